@@ -0,0 +1,63 @@
+// Check the riddle content pack at build time, so that every difficulty has a riddle and no
+// answer can clash with a rule that bans a phrase from the password.
+
+use std::fs;
+
+/// The phrase the bean rule forbids. The other banned phrases are the countries of the texts to
+/// quote from, which are read from src/corpus.rs so that new texts are covered too.
+const BEAN: &str = "bean";
+
+const DIFFICULTIES: [&str; 3] = ["easy", "normal", "hard"];
+
+/// The country of every text in `CORPORA`, lowercased.
+fn countries() -> Vec<String> {
+    let corpus = fs::read_to_string("src/corpus.rs").expect("src/corpus.rs should be readable");
+    let countries: Vec<_> = corpus
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("country: \""))
+        .filter_map(|rest| rest.split_once('"'))
+        .map(|(country, _)| country.to_lowercase())
+        .collect();
+    assert!(
+        !countries.is_empty(),
+        "no corpus countries found in src/corpus.rs"
+    );
+    countries
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/corpus.rs");
+    println!("cargo:rerun-if-changed=src/riddles");
+    let mut banned = countries();
+    banned.push(BEAN.to_string());
+    let riddles = fs::read_to_string("src/riddles").expect("src/riddles should be readable");
+    let mut counts = [0; DIFFICULTIES.len()];
+    for line in riddles
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        let mut fields = line.split('|');
+        let key = fields.next().unwrap_or_default();
+        let difficulty = fields.next().unwrap_or_default();
+        let Some(index) = DIFFICULTIES.iter().position(|known| *known == difficulty) else {
+            panic!("riddle {key} has an unknown difficulty: {difficulty:?}");
+        };
+        counts[index] += 1;
+        let answers: Vec<_> = fields.collect();
+        assert!(!answers.is_empty(), "riddle {key} has no answers");
+        for answer in answers {
+            if let Some(banned) = banned
+                .iter()
+                .find(|banned| answer.to_lowercase().contains(banned.as_str()))
+            {
+                panic!(
+                    "the answer {answer:?} to riddle {key} contains the banned phrase {banned:?}"
+                );
+            }
+        }
+    }
+    for (difficulty, count) in DIFFICULTIES.iter().zip(counts) {
+        assert!(count > 0, "there are no {difficulty} riddles");
+    }
+}
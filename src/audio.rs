@@ -0,0 +1,83 @@
+use web_sys::wasm_bindgen::JsValue;
+use web_sys::{AudioContext, AudioScheduledSourceNode, OscillatorType};
+use yew::{function_component, html, use_context, use_effect_with, use_mut_ref, Html, Properties};
+
+use crate::i18n::text;
+use crate::locale::Locale;
+
+/// Length of a Morse dot in seconds. A dash is three dots long.
+const UNIT: f64 = 0.08;
+const PITCH: f32 = 600.0;
+
+/// Beep some Morse code. Symbols are a unit apart, letters three and words seven.
+fn play(context: &AudioContext, code: &str) -> Result<(), JsValue> {
+    let oscillator = context.create_oscillator()?;
+    let gain = context.create_gain()?;
+    oscillator.set_type(OscillatorType::Sine);
+    oscillator.frequency().set_value(PITCH);
+    gain.gain().set_value(0.0);
+    oscillator.connect_with_audio_node(&gain)?;
+    gain.connect_with_audio_node(&context.destination())?;
+    let mut time = context.current_time();
+    for symbol in code.chars() {
+        match symbol {
+            '.' | '-' => {
+                let length = if symbol == '.' { UNIT } else { UNIT * 3.0 };
+                gain.gain().set_value_at_time(0.5, time)?;
+                gain.gain().set_value_at_time(0.0, time + length)?;
+                time += length + UNIT;
+            }
+            ' ' => time += UNIT * 2.0,
+            '/' => time += UNIT * 4.0,
+            _ => {}
+        }
+    }
+    AudioScheduledSourceNode::start(&oscillator)?;
+    AudioScheduledSourceNode::stop_with_when(&oscillator, time)?;
+    Ok(())
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MorsePlayerProps {
+    pub code: String,
+}
+
+/// A button that plays Morse code out loud.
+#[function_component]
+pub fn MorsePlayer(props: &MorsePlayerProps) -> Html {
+    let locale = use_context::<Locale>().unwrap_or(Locale::English);
+    // One audio context for every play, made on the first click since browsers only let audio
+    // start from a gesture, and closed when the button goes away
+    let context = use_mut_ref(|| None::<AudioContext>);
+    use_effect_with((), {
+        let context = context.clone();
+        move |()| {
+            move || {
+                if let Some(context) = context.borrow_mut().take() {
+                    let _ = context.close();
+                }
+            }
+        }
+    });
+    let onclick = {
+        let code = props.code.clone();
+        move |_| {
+            let mut context = context.borrow_mut();
+            if context.is_none() {
+                *context = AudioContext::new().ok();
+            }
+            if let Some(context) = context.as_ref() {
+                let _ = play(context, &code);
+            }
+        }
+    };
+
+    html! {
+        <button
+            onclick={onclick}
+            class="self-start p-2 transition bg-white border border-gray-700 rounded-xl hover:bg-gray-200"
+        >
+            {text(locale, "morse.play", &[])}
+        </button>
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Duration, Local};
+use gloo_timers::callback::Interval;
+use hmac::{Hmac, Mac};
+use rand::{thread_rng, Rng};
+use sha1::Sha1;
+use yew::{function_component, html, use_effect_with, use_state, Html, Properties};
+
+/// Length of a TOTP time step in seconds (RFC 6238 default).
+pub const STEP: i64 = 30;
+
+pub type Secret = [u8; 20];
+
+/// Generate a fresh random shared secret.
+pub fn secret() -> Secret {
+    thread_rng().gen()
+}
+
+/// Compute the 6-digit TOTP code for the given secret at the given time (RFC 6238, HMAC-SHA1).
+pub fn code(secret: &Secret, time: DateTime<Local>) -> String {
+    let counter = time.timestamp().div_euclid(STEP);
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+    // Dynamic truncation as described in RFC 4226
+    let offset = usize::from(hash[hash.len() - 1] & 0xf);
+    let binary = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    format!("{:06}", binary % 1_000_000)
+}
+
+/// Whether a typed code is valid at the given time. The code from the step before counts too,
+/// so a code typed just before it rolls over isn't rejected (RFC 6238, section 5.2).
+pub fn verify(secret: &Secret, attempt: &str, time: DateTime<Local>) -> bool {
+    [time, time - Duration::seconds(STEP)]
+        .into_iter()
+        .any(|moment| attempt == code(secret, moment))
+}
+
+/// Seconds left until the code rolls over.
+pub fn remaining(time: &DateTime<Local>) -> i64 {
+    STEP - time.timestamp().rem_euclid(STEP)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AuthenticatorProps {
+    pub secret: Secret,
+}
+
+#[function_component]
+pub fn Authenticator(props: &AuthenticatorProps) -> Html {
+    let now = use_state(Local::now);
+    use_effect_with((), {
+        let now = now.clone();
+        move |()| {
+            let interval = Interval::new(1000, move || now.set(Local::now()));
+            move || drop(interval)
+        }
+    });
+    html! {
+        <div class="flex items-center justify-between p-4 text-white bg-slate-600 rounded-xl">
+            <span class="font-mono text-3xl tracking-widest">{code(&props.secret, *now)}</span>
+            <span class="text-sm">{format!("{}s", remaining(&now))}</span>
+        </div>
+    }
+}
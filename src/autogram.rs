@@ -0,0 +1,125 @@
+use rand::{prelude::SliceRandom, thread_rng};
+
+use crate::i18n::text;
+use crate::locale::Locale;
+use crate::numerals::cardinal;
+
+/// The largest number looked for when working out which count a password states.
+const LARGEST: usize = 300;
+
+/// What a self-describing password has to count.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Autogram {
+    /// Occurrences of one letter, in either case
+    Letter(char),
+    /// Every character
+    Length,
+}
+
+impl Autogram {
+    pub fn generate() -> Self {
+        *[
+            Self::Letter('e'),
+            Self::Letter('t'),
+            Self::Letter('n'),
+            Self::Letter('r'),
+            Self::Length,
+        ]
+        .choose(&mut thread_rng())
+        .unwrap()
+    }
+
+    fn count(self, password: &str) -> usize {
+        match self {
+            Self::Letter(letter) => password
+                .chars()
+                .filter(|char| char.to_lowercase().eq(letter.to_lowercase()))
+                .count(),
+            Self::Length => password.chars().count(),
+        }
+    }
+
+    /// A number written in words, in any of the game's languages.
+    fn spellings(number: usize) -> Vec<String> {
+        Locale::ALL
+            .into_iter()
+            .map(|locale| cardinal(number as i128, locale).to_lowercase())
+            .collect()
+    }
+
+    pub fn accepts(self, password: &str) -> bool {
+        let password = password.to_lowercase();
+        Self::spellings(self.count(&password))
+            .iter()
+            .any(|spelling| find_word(&password, spelling).is_some())
+    }
+
+    /// The count the password states, as the number and how it is spelled. The longest spelling wins,
+    /// so that "twenty-one" is not read as "one".
+    fn stated(self, password: &str) -> Option<(usize, String)> {
+        let count = self.count(password);
+        (0..=LARGEST)
+            .flat_map(|number| {
+                Self::spellings(number)
+                    .into_iter()
+                    .map(move |spelling| (number, spelling))
+            })
+            .filter(|(_, spelling)| find_word(password, spelling).is_some())
+            .max_by_key(|(number, spelling)| (spelling.len(), usize::MAX - number.abs_diff(count)))
+    }
+
+    pub fn message(self, locale: Locale) -> String {
+        match self {
+            Self::Letter(letter) => text(
+                locale,
+                "rule.autogram-letter",
+                &[("letter", &letter.to_string())],
+            ),
+            Self::Length => text(locale, "rule.autogram-length", &[]),
+        }
+    }
+
+    /// How far off the stated count is. Writing the right number in changes the count itself,
+    /// so this also works out whether swapping it in would settle on the right answer.
+    pub fn feedback(self, password: &str, locale: Locale) -> String {
+        let password = password.to_lowercase();
+        let count = self.count(&password);
+        let spell = |number: usize| cardinal(number as i128, locale);
+        let Some((stated, spelling)) = self.stated(&password) else {
+            return text(locale, "autogram.none", &[("count", &spell(count))]);
+        };
+        let mut replaced = password.clone();
+        if let Some(index) = find_word(&password, &spelling) {
+            replaced.replace_range(index..index + spelling.len(), &spell(count).to_lowercase());
+        }
+        let swapped = self.count(&replaced);
+        let mut feedback = vec![text(
+            locale,
+            "autogram.off",
+            &[("stated", &spell(stated)), ("count", &spell(count))],
+        )];
+        feedback.push(if swapped == count {
+            text(locale, "autogram.swap-works", &[("count", &spell(count))])
+        } else {
+            text(
+                locale,
+                "autogram.swap-changes",
+                &[("count", &spell(count)), ("swapped", &spell(swapped))],
+            )
+        });
+        feedback.join(" ")
+    }
+}
+
+/// Where some spelling of a number first appears as a word of its own, rather than inside a
+/// longer word, like "one" in "phone" or "elf" in "self". Hyphens join words, so the "one" of
+/// "twenty-one" doesn't count on its own either.
+fn find_word(text: &str, spelling: &str) -> Option<usize> {
+    let joined = |char: Option<char>| char.is_some_and(|char| char.is_alphabetic() || char == '-');
+    text.match_indices(spelling)
+        .map(|(index, _)| index)
+        .find(|&index| {
+            !joined(text[..index].chars().next_back())
+                && !joined(text[index + spelling.len()..].chars().next())
+        })
+}
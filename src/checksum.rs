@@ -0,0 +1,111 @@
+use rand::{thread_rng, Rng};
+
+use crate::i18n::text;
+use crate::locale::Locale;
+
+/// Number of digits an ISBN-10 check symbol covers.
+const ISBN_DIGITS: usize = 9;
+
+fn digits(text: &str) -> Vec<u32> {
+    text.chars().filter_map(|char| char.to_digit(10)).collect()
+}
+
+/// The Luhn sum modulo ten, which is zero for a valid number. Every second digit from the right is
+/// doubled, and has nine taken off if that makes it more than nine.
+pub fn luhn(digits: &[u32]) -> u32 {
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, &digit)| match (index % 2, digit * 2) {
+            (0, _) => digit,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum::<u32>()
+        % 10
+}
+
+/// The ISBN-10 check symbol for nine digits: weight them from ten down to two, and pick the symbol
+/// that makes the total a multiple of eleven, with X standing for ten.
+pub fn isbn(digits: &[u32]) -> char {
+    let sum: u32 = (2..=10)
+        .rev()
+        .zip(digits)
+        .map(|(weight, digit)| weight * digit)
+        .sum();
+    match (11 - sum % 11) % 11 {
+        10 => 'X',
+        check => char::from_digit(check, 10).unwrap(),
+    }
+}
+
+/// A check the digits of a password have to pass.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Checksum {
+    /// All of its digits together pass the Luhn check
+    Luhn,
+    /// It ends with the ISBN-10 check symbol of the nine digits before
+    Isbn,
+}
+
+impl Checksum {
+    pub fn generate() -> Self {
+        if thread_rng().gen_bool(0.5) {
+            Self::Luhn
+        } else {
+            Self::Isbn
+        }
+    }
+
+    /// The nine digits before the last character, if there are that many.
+    fn isbn_digits(password: &str) -> Option<Vec<u32>> {
+        let mut chars = password.chars();
+        chars.next_back()?;
+        let digits = digits(chars.as_str());
+        (digits.len() >= ISBN_DIGITS).then(|| digits[digits.len() - ISBN_DIGITS..].to_vec())
+    }
+
+    pub fn accepts(self, password: &str) -> bool {
+        match self {
+            Self::Luhn => {
+                let digits = digits(password);
+                !digits.is_empty() && luhn(&digits) == 0
+            }
+            Self::Isbn => Self::isbn_digits(password).is_some_and(|digits| {
+                password
+                    .chars()
+                    .last()
+                    .is_some_and(|last| last.to_ascii_uppercase() == isbn(&digits))
+            }),
+        }
+    }
+
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Luhn => "rule.luhn",
+            Self::Isbn => "rule.isbn",
+        }
+    }
+
+    /// The check value the password currently works out to.
+    pub fn hint(self, password: &str, locale: Locale) -> String {
+        match self {
+            Self::Luhn => text(
+                locale,
+                "checksum.luhn",
+                &[("sum", &luhn(&digits(password)).to_string())],
+            ),
+            Self::Isbn => Self::isbn_digits(password).map_or_else(
+                || text(locale, "checksum.isbn-short", &[]),
+                |digits| {
+                    text(
+                        locale,
+                        "checksum.isbn",
+                        &[("check", &isbn(&digits).to_string())],
+                    )
+                },
+            ),
+        }
+    }
+}
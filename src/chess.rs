@@ -0,0 +1,546 @@
+use std::rc::Rc;
+
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+use yew::{classes, function_component, html, use_context, Html, Properties};
+
+use crate::i18n::text;
+use crate::locale::Locale;
+use crate::settings::Difficulty;
+
+/// A square as (file, rank), both counted from zero, so a1 is (0, 0).
+pub type Square = (i8, i8);
+
+const STRAIGHT: [Square; 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const DIAGONAL: [Square; 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+const ALL: [Square; 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+/// How many random positions to try before settling for a puzzle that is known to work.
+const ATTEMPTS: usize = 500;
+
+const KNIGHT: [Square; 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    King,
+    Queen,
+    Rook,
+    Bishop,
+    Knight,
+}
+
+impl Kind {
+    /// The letter used for the piece in algebraic notation.
+    const fn letter(self) -> char {
+        match self {
+            Self::King => 'K',
+            Self::Queen => 'Q',
+            Self::Rook => 'R',
+            Self::Bishop => 'B',
+            Self::Knight => 'N',
+        }
+    }
+
+    const fn symbol(self, white: bool) -> char {
+        match (self, white) {
+            (Self::King, true) => '\u{2654}',
+            (Self::Queen, true) => '\u{2655}',
+            (Self::Rook, true) => '\u{2656}',
+            (Self::Bishop, true) => '\u{2657}',
+            (Self::Knight, true) => '\u{2658}',
+            (Self::King, false) => '\u{265A}',
+            (Self::Queen, false) => '\u{265B}',
+            (Self::Rook, false) => '\u{265C}',
+            (Self::Bishop, false) => '\u{265D}',
+            (Self::Knight, false) => '\u{265E}',
+        }
+    }
+
+    /// Catalogue key for the name of the piece.
+    const fn key(self) -> &'static str {
+        match self {
+            Self::King => "chess.king",
+            Self::Queen => "chess.queen",
+            Self::Rook => "chess.rook",
+            Self::Bishop => "chess.bishop",
+            Self::Knight => "chess.knight",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Piece {
+    pub kind: Kind,
+    pub white: bool,
+    pub square: Square,
+}
+
+const fn on_board((file, rank): Square) -> bool {
+    0 <= file && file < 8 && 0 <= rank && rank < 8
+}
+
+/// The name of a square, e.g. `e4`.
+#[allow(clippy::cast_sign_loss)]
+pub fn name((file, rank): Square) -> String {
+    format!("{}{}", (b'a' + file as u8) as char, rank + 1)
+}
+
+/// A position with White to move. Both sides must have a king.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Position {
+    pub pieces: Vec<Piece>,
+}
+
+impl Position {
+    fn at(&self, square: Square) -> Option<&Piece> {
+        self.pieces.iter().find(|piece| piece.square == square)
+    }
+
+    fn king(&self, white: bool) -> Square {
+        self.pieces
+            .iter()
+            .find(|piece| piece.kind == Kind::King && piece.white == white)
+            .unwrap()
+            .square
+    }
+
+    /// Every square a piece attacks, stopping sliding pieces at the first piece in their way.
+    fn attacks(&self, piece: Piece) -> Vec<Square> {
+        let (directions, slides): (&[Square], bool) = match piece.kind {
+            Kind::King => (&ALL, false),
+            Kind::Queen => (&ALL, true),
+            Kind::Rook => (&STRAIGHT, true),
+            Kind::Bishop => (&DIAGONAL, true),
+            Kind::Knight => (&KNIGHT, false),
+        };
+        let mut squares = Vec::new();
+        for &(file, rank) in directions {
+            let mut square = piece.square;
+            loop {
+                square = (square.0 + file, square.1 + rank);
+                if !on_board(square) {
+                    break;
+                }
+                squares.push(square);
+                if !slides || self.at(square).is_some() {
+                    break;
+                }
+            }
+        }
+        squares
+    }
+
+    fn attacked(&self, square: Square, by_white: bool) -> bool {
+        self.pieces
+            .iter()
+            .filter(|piece| piece.white == by_white)
+            .any(|piece| self.attacks(*piece).contains(&square))
+    }
+
+    fn in_check(&self, white: bool) -> bool {
+        self.attacked(self.king(white), !white)
+    }
+
+    /// The position after moving a piece, capturing whatever was on the destination.
+    fn moved(&self, from: Square, to: Square) -> Self {
+        let mut pieces: Vec<_> = self
+            .pieces
+            .iter()
+            .filter(|piece| piece.square != to)
+            .copied()
+            .collect();
+        for piece in &mut pieces {
+            if piece.square == from {
+                piece.square = to;
+            }
+        }
+        Self { pieces }
+    }
+
+    /// Every legal move for one side, as (from, to). There are no pawns, so no castling, en
+    /// passant or promotion either.
+    fn moves(&self, white: bool) -> Vec<(Square, Square)> {
+        self.pieces
+            .iter()
+            .filter(|piece| piece.white == white)
+            .flat_map(|piece| {
+                self.attacks(*piece)
+                    .into_iter()
+                    .map(move |to| (piece.square, to))
+            })
+            .filter(|&(_, to)| {
+                self.at(to)
+                    .is_none_or(|piece| piece.white != white && piece.kind != Kind::King)
+            })
+            .filter(|&(from, to)| !self.moved(from, to).in_check(white))
+            .collect()
+    }
+
+    /// Whether a move by White leaves Black checkmated.
+    fn mates(&self, from: Square, to: Square) -> bool {
+        self.forces_mate(from, to, 1)
+    }
+
+    /// Whether a move by White mates within the given number of White moves, however Black
+    /// replies.
+    fn forces_mate(&self, from: Square, to: Square, depth: usize) -> bool {
+        let after = self.moved(from, to);
+        let replies = after.moves(false);
+        if replies.is_empty() {
+            // Without a check this is stalemate, which is no win at all
+            return after.in_check(false);
+        }
+        depth > 1
+            && replies.into_iter().all(|(from, to)| {
+                let reply = after.moved(from, to);
+                reply
+                    .moves(true)
+                    .into_iter()
+                    .any(|(from, to)| reply.forces_mate(from, to, depth - 1))
+            })
+    }
+
+    /// A move by White in standard algebraic notation, e.g. `Rae8#`.
+    pub fn san(&self, from: Square, to: Square) -> String {
+        let piece = self.at(from).unwrap();
+        let mut san = piece.kind.letter().to_string();
+        // Say which piece moves when another of the same kind could move there too
+        let rivals: Vec<_> = self
+            .moves(true)
+            .into_iter()
+            .filter(|&(other, destination)| {
+                destination == to
+                    && other != from
+                    && self.at(other).is_some_and(|other| other.kind == piece.kind)
+            })
+            .map(|(other, _)| other)
+            .collect();
+        if !rivals.is_empty() {
+            let square = name(from);
+            if rivals.iter().all(|rival| rival.0 != from.0) {
+                san.push_str(&square[..1]);
+            } else if rivals.iter().all(|rival| rival.1 != from.1) {
+                san.push_str(&square[1..]);
+            } else {
+                san.push_str(&square);
+            }
+        }
+        if self.at(to).is_some() {
+            san.push('x');
+        }
+        san.push_str(&name(to));
+        if self.mates(from, to) {
+            san.push('#');
+        } else if self.moved(from, to).in_check(false) {
+            san.push('+');
+        }
+        san
+    }
+
+    /// A lone black king against a white king and two other pieces, where White has exactly one
+    /// move that mates in the given number of moves, and no quicker mate. Returns the position
+    /// and that move, falling back to a known puzzle if none turns up.
+    pub fn puzzle(depth: usize) -> (Self, String) {
+        let mut rng = thread_rng();
+        for _ in 0..ATTEMPTS {
+            if let Some(puzzle) = Self::random(&mut rng).and_then(|position| position.solve(depth))
+            {
+                return puzzle;
+            }
+        }
+        Self::known(depth).solve(depth).unwrap()
+    }
+
+    /// A random position with Black not in check, if the pieces didn't land on each other.
+    fn random(rng: &mut impl Rng) -> Option<Self> {
+        // A king on the edge of the board is far easier to mate
+        let edge = rng.gen_range(0..8);
+        let black = *[(edge, 0), (edge, 7), (0, edge), (7, edge)]
+            .choose(rng)
+            .unwrap();
+        let mut position = Self {
+            pieces: vec![Piece {
+                kind: Kind::King,
+                white: false,
+                square: black,
+            }],
+        };
+        let others = [Kind::Queen, Kind::Rook, Kind::Bishop, Kind::Knight];
+        for kind in [
+            Kind::King,
+            *others.choose(rng).unwrap(),
+            *others.choose(rng).unwrap(),
+        ] {
+            let square = (rng.gen_range(0..8), rng.gen_range(0..8));
+            if position.at(square).is_some() {
+                return None;
+            }
+            position.pieces.push(Piece {
+                kind,
+                white: true,
+                square,
+            });
+        }
+        (!position.in_check(false) && !position.in_check(true)).then_some(position)
+    }
+
+    /// The only move that mates in the given number of moves, in algebraic notation, as long as
+    /// there is no quicker mate.
+    fn solve(self, depth: usize) -> Option<(Self, String)> {
+        let mut solutions = self
+            .moves(true)
+            .into_iter()
+            .filter(|&(from, to)| self.forces_mate(from, to, depth));
+        let (from, to) = solutions.next()?;
+        if solutions.next().is_some() || (depth > 1 && self.mates(from, to)) {
+            return None;
+        }
+        let san = self.san(from, to);
+        Some((self, san))
+    }
+
+    /// A puzzle to fall back on: mate in one with a rook along the back rank, or in two by
+    /// boxing the king in with one rook before mating with the other.
+    fn known(depth: usize) -> Self {
+        let piece = |kind, white, square| Piece {
+            kind,
+            white,
+            square,
+        };
+        Self {
+            pieces: if depth > 1 {
+                vec![
+                    piece(Kind::King, false, (0, 6)),
+                    piece(Kind::King, true, (2, 1)),
+                    piece(Kind::Rook, true, (4, 4)),
+                    piece(Kind::Rook, true, (2, 5)),
+                ]
+            } else {
+                vec![
+                    piece(Kind::King, false, (6, 7)),
+                    piece(Kind::King, true, (6, 5)),
+                    piece(Kind::Rook, true, (0, 0)),
+                    piece(Kind::Knight, true, (1, 0)),
+                ]
+            },
+        }
+    }
+
+    /// How many moves the puzzle for a difficulty takes to mate.
+    pub const fn depth(difficulty: Difficulty) -> usize {
+        match difficulty {
+            Difficulty::Easy | Difficulty::Normal => 1,
+            Difficulty::Hard => 2,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ChessboardProps {
+    pub position: Rc<Position>,
+}
+
+/// A chessboard with White at the bottom.
+#[function_component]
+pub fn Chessboard(props: &ChessboardProps) -> Html {
+    let locale = use_context::<Locale>().unwrap_or(Locale::English);
+    let position = &props.position;
+    let description: Vec<_> = position
+        .pieces
+        .iter()
+        .map(|piece| {
+            text(
+                locale,
+                "chess.piece",
+                &[
+                    (
+                        "colour",
+                        &text(
+                            locale,
+                            if piece.white {
+                                "chess.white"
+                            } else {
+                                "chess.black"
+                            },
+                            &[],
+                        ),
+                    ),
+                    ("kind", &text(locale, piece.kind.key(), &[])),
+                    ("square", &name(piece.square)),
+                ],
+            )
+        })
+        .collect();
+    let squares = (0..8)
+        .rev()
+        .flat_map(|rank| {
+            (0..8).map(move |file| {
+                let square = (file, rank);
+                let label = name(square);
+                html! {
+                    <div class={classes!(
+                        "relative", "grid", "text-3xl", "place-content-center", "aspect-square",
+                        if (file + rank) % 2 == 0 { "bg-amber-700" } else { "bg-amber-100" },
+                    )}>
+                        {
+                            position.at(square).map(|piece| piece.kind.symbol(piece.white).to_string())
+                        }
+                        // Label the files along the bottom and the ranks up the left
+                        {
+                            (rank == 0).then(|| html! {
+                                <span class="absolute bottom-0 right-0.5 text-[0.5rem] leading-none">
+                                    {label[..1].to_string()}
+                                </span>
+                            })
+                        }
+                        {
+                            (file == 0).then(|| html! {
+                                <span class="absolute top-0 left-0.5 text-[0.5rem] leading-none">
+                                    {label[1..].to_string()}
+                                </span>
+                            })
+                        }
+                    </div>
+                }
+            })
+        })
+        .collect::<Html>();
+
+    html! {
+        <div
+            role="img"
+            aria-label={text(locale, "chess.label", &[("pieces", &description.join(", "))])}
+            class="grid grid-cols-8 w-64 border-4 border-slate-600"
+        >
+            {squares}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse a square name, e.g. `e4`.
+    #[allow(clippy::cast_possible_wrap)]
+    fn square(name: &str) -> Square {
+        let name = name.as_bytes();
+        ((name[0] - b'a') as i8, (name[1] - b'1') as i8)
+    }
+
+    /// Parse a position from pieces like `wKe1`, for white king on e1.
+    fn position(pieces: &str) -> Position {
+        Position {
+            pieces: pieces
+                .split_whitespace()
+                .map(|piece| Piece {
+                    kind: match &piece[1..2] {
+                        "K" => Kind::King,
+                        "Q" => Kind::Queen,
+                        "R" => Kind::Rook,
+                        "B" => Kind::Bishop,
+                        _ => Kind::Knight,
+                    },
+                    white: piece.starts_with('w'),
+                    square: square(&piece[2..]),
+                })
+                .collect(),
+        }
+    }
+
+    /// Count the leaf nodes of the move tree to some depth, with White to move first.
+    fn perft(position: &Position, white: bool, depth: usize) -> usize {
+        if depth == 0 {
+            return 1;
+        }
+        position
+            .moves(white)
+            .into_iter()
+            .map(|(from, to)| perft(&position.moved(from, to), !white, depth - 1))
+            .sum()
+    }
+
+    #[test]
+    fn perft_counts() {
+        for (pieces, depth, count) in [
+            ("wKe4 bKe8", 1, 8),
+            ("wKe4 bKe8", 2, 40),
+            ("wKa1 wRd4 bKh8", 1, 17),
+            ("wKa1 wQd4 bKh7", 1, 29),
+            ("wKa1 wNd4 bKh8", 1, 11),
+            // The rook is pinned to its king, so it can only move along the file
+            ("wKe1 wRe4 bRe8 bKa8", 1, 11),
+        ] {
+            assert_eq!(perft(&position(pieces), true, depth), count, "{pieces}");
+        }
+    }
+
+    #[test]
+    fn mates() {
+        let back_rank = position("wKg6 wRa1 bKh8");
+        assert!(back_rank.mates(square("a1"), square("a8")));
+        assert!(!back_rank.mates(square("a1"), square("a7")));
+        let queen = position("wKf7 wQe6 bKh8");
+        assert!(queen.mates(square("e6"), square("g8")));
+        // Stalemate is not mate
+        assert!(!queen.mates(square("e6"), square("g6")));
+    }
+
+    #[test]
+    fn mates_in_two() {
+        let rooks = position("wKc2 wRe5 wRc6 bKa7");
+        assert!(rooks.forces_mate(square("e5"), square("b5"), 2));
+        assert!(!rooks.mates(square("e5"), square("b5")));
+    }
+
+    #[test]
+    fn san() {
+        let rooks = position("wKe5 wRa1 wRh1 wRa5 bNd1 bKg8");
+        assert_eq!(rooks.san(square("a1"), square("b1")), "Rb1");
+        assert_eq!(rooks.san(square("a1"), square("a3")), "R1a3");
+        assert_eq!(rooks.san(square("a1"), square("d1")), "Raxd1");
+        assert_eq!(rooks.san(square("h1"), square("d1")), "Rhxd1");
+        assert_eq!(rooks.san(square("e5"), square("d4")), "Kd4");
+        assert_eq!(rooks.san(square("h1"), square("g1")), "Rg1+");
+        assert_eq!(
+            position("wKg6 wRa1 bKh8").san(square("a1"), square("a8")),
+            "Ra8#"
+        );
+    }
+
+    #[test]
+    fn solutions_must_be_unique() {
+        assert!(position("wKg6 wRa1 wRb2 bKh8").solve(1).is_none());
+        assert_eq!(
+            position("wKg6 wRa1 wNb1 bKh8").solve(1).map(|(_, san)| san),
+            Some("Ra8#".to_string())
+        );
+        // A mate in one is not a mate in two
+        assert!(position("wKg6 wRa1 wNb1 bKh8").solve(2).is_none());
+    }
+
+    #[test]
+    fn puzzles() {
+        for depth in [1, 2] {
+            assert!(Position::known(depth).solve(depth).is_some());
+            let (position, answer) = Position::puzzle(depth);
+            assert_eq!(position.solve(depth).map(|(_, san)| san), Some(answer));
+        }
+    }
+}
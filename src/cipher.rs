@@ -0,0 +1,75 @@
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+
+use crate::i18n::text;
+use crate::locale::Locale;
+use crate::settings::Difficulty;
+
+/// A classical cipher over the letters a to z. Anything else is left alone.
+pub enum Cipher {
+    /// Shift every letter along the alphabet, saying by how much unless the key is hidden
+    Caesar { shift: u8, hidden: bool },
+    /// Swap the alphabet end to end
+    Atbash,
+    /// A Caesar shift that changes with every letter, following the letters of a key
+    Vigenere(String),
+}
+
+/// Apply a function to the position in the alphabet of every lowercase letter.
+fn letters(text: &str, mut map: impl FnMut(u8) -> u8) -> String {
+    text.bytes()
+        .map(|byte| {
+            if byte.is_ascii_lowercase() {
+                (b'a' + map(byte - b'a') % 26) as char
+            } else {
+                byte as char
+            }
+        })
+        .collect()
+}
+
+impl Cipher {
+    /// Easy games get a visible Caesar shift, normal games a hidden one or Atbash, and hard games
+    /// Vigenère.
+    pub fn generate(difficulty: Difficulty) -> Self {
+        let shift = thread_rng().gen_range(1..26);
+        match difficulty {
+            Difficulty::Easy => Self::Caesar {
+                shift,
+                hidden: false,
+            },
+            Difficulty::Normal if thread_rng().gen_bool(0.5) => Self::Atbash,
+            Difficulty::Normal => Self::Caesar {
+                shift,
+                hidden: true,
+            },
+            Difficulty::Hard => {
+                let words: Vec<_> = include_str!("words").lines().collect();
+                Self::Vigenere((*words.choose(&mut thread_rng()).unwrap()).to_string())
+            }
+        }
+    }
+
+    pub fn encipher(&self, plaintext: &str) -> String {
+        match self {
+            Self::Caesar { shift, .. } => letters(plaintext, |letter| letter + shift),
+            Self::Atbash => letters(plaintext, |letter| 25 - letter),
+            Self::Vigenere(key) => {
+                let mut key = key.bytes().cycle();
+                letters(plaintext, |letter| letter + key.next().unwrap() - b'a')
+            }
+        }
+    }
+
+    /// How the word was enciphered, as much as the player is told.
+    pub fn describe(&self, locale: Locale) -> String {
+        match self {
+            Self::Caesar {
+                shift,
+                hidden: false,
+            } => text(locale, "cipher.caesar", &[("shift", &shift.to_string())]),
+            Self::Caesar { hidden: true, .. } => text(locale, "cipher.caesar-hidden", &[]),
+            Self::Atbash => text(locale, "cipher.atbash", &[]),
+            Self::Vigenere(key) => text(locale, "cipher.vigenere", &[("key", key)]),
+        }
+    }
+}
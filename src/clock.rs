@@ -0,0 +1,254 @@
+use std::f64::consts::TAU;
+
+use rand::{thread_rng, Rng};
+use yew::{function_component, html, use_context, Html, Properties};
+
+use crate::i18n::{plural, text};
+use crate::locale::Locale;
+use crate::numerals::cardinal;
+
+/// How the time on the clock has to be written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `3:05`, whether it is morning or afternoon
+    Twelve,
+    /// `15:05`, so the player has to be told which half of the day it is
+    TwentyFour,
+}
+
+/// A time shown on an analog clock, and what the player has to make of it.
+pub struct Reading {
+    /// Hour of the day, from 0 to 23
+    pub hour: u32,
+    pub minute: u32,
+    pub format: Format,
+    /// Minutes to add to the shown time before writing it down
+    pub later: u32,
+}
+
+impl Reading {
+    pub fn generate() -> Self {
+        let mut rng = thread_rng();
+        Self {
+            hour: rng.gen_range(0..24),
+            minute: rng.gen_range(0..60),
+            format: if rng.gen_bool(0.5) {
+                Format::Twelve
+            } else {
+                Format::TwentyFour
+            },
+            later: if rng.gen_bool(0.5) {
+                rng.gen_range(1..=12) * 5
+            } else {
+                0
+            },
+        }
+    }
+
+    /// The time the password must contain, e.g. `3:05` or `15:05`.
+    pub fn answer(&self) -> String {
+        let total = (self.hour * 60 + self.minute + self.later) % (24 * 60);
+        let (hour, minute) = (total / 60, total % 60);
+        match self.format {
+            Format::Twelve => format!("{}:{minute:02}", (hour + 11) % 12 + 1),
+            Format::TwentyFour => format!("{hour}:{minute:02}"),
+        }
+    }
+
+    /// The catalogue key for the part of the day the shown time is in, which tells the two
+    /// halves of the day apart.
+    const fn period(&self) -> &'static str {
+        match self.hour {
+            0..=4 => "clock.night",
+            5..=11 => "clock.morning",
+            12..=17 => "clock.afternoon",
+            _ => "clock.evening",
+        }
+    }
+
+    pub fn message(&self, locale: Locale) -> String {
+        let mut message = vec![text(locale, "rule.clock", &[])];
+        if self.format == Format::TwentyFour {
+            message.push(text(locale, "clock.24-hour", &[]));
+            message.push(text(locale, self.period(), &[]));
+        }
+        if self.later > 0 {
+            message.push(plural(
+                locale,
+                "clock.later",
+                self.later as usize,
+                &[("count", &cardinal(self.later.into(), locale))],
+            ));
+        }
+        message.join(" ")
+    }
+}
+
+/// Angles of the hour and minute hands, in degrees clockwise from twelve.
+pub fn hands(hour: u32, minute: u32) -> (f64, f64) {
+    (
+        f64::from(hour % 12).mul_add(30.0, f64::from(minute) / 2.0),
+        f64::from(minute) * 6.0,
+    )
+}
+
+/// The end of a hand of the given length, on a clock face centred on (50, 50).
+fn point(degrees: f64, length: f64) -> (f64, f64) {
+    let radians = degrees / 360.0 * TAU;
+    (
+        length.mul_add(radians.sin(), 50.0),
+        length.mul_add(-radians.cos(), 50.0),
+    )
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AnalogClockProps {
+    pub hour: u32,
+    pub minute: u32,
+}
+
+/// A clock face with hour and minute hands, drawn as an SVG.
+#[function_component]
+pub fn AnalogClock(props: &AnalogClockProps) -> Html {
+    let locale = use_context::<Locale>().unwrap_or(Locale::English);
+    let (hour, minute) = hands(props.hour, props.minute);
+    let marks = (0..12)
+        .map(|mark| {
+            let (x1, y1) = point(f64::from(mark) * 30.0, 40.0);
+            let (x2, y2) = point(
+                f64::from(mark) * 30.0,
+                if mark % 3 == 0 { 32.0 } else { 36.0 },
+            );
+            html! {
+                <line
+                    x1={x1.to_string()} y1={y1.to_string()}
+                    x2={x2.to_string()} y2={y2.to_string()}
+                    stroke="white" stroke-width="2"
+                />
+            }
+        })
+        .collect::<Html>();
+    let hand = |degrees: f64, length: f64, width: &'static str| {
+        let (x, y) = point(degrees, length);
+        html! {
+            <line
+                x1="50" y1="50" x2={x.to_string()} y2={y.to_string()}
+                stroke="white" stroke-width={width} stroke-linecap="round"
+            />
+        }
+    };
+
+    html! {
+        <svg
+            viewBox="0 0 100 100"
+            role="img"
+            aria-label={text(
+                locale,
+                "clock.label",
+                &[("hour", &format!("{hour:.0}")), ("minute", &format!("{minute:.0}"))],
+            )}
+            class="w-48 h-48"
+        >
+            <circle cx="50" cy="50" r="45" class="fill-slate-600" />
+            {marks}
+            {hand(hour, 22.0, "4")}
+            {hand(minute, 34.0, "2")}
+            <circle cx="50" cy="50" r="3" fill="white" />
+        </svg>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Read the hands the way a player would, then follow the rule's instructions.
+    fn read(reading: &Reading) -> String {
+        let (hour, minute) = hands(reading.hour, reading.minute);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (mut hour, minute) = ((hour / 30.0).floor() as u32, (minute / 6.0).round() as u32);
+        if reading.format == Format::TwentyFour {
+            hour += match reading.period() {
+                "clock.afternoon" | "clock.evening" => 12,
+                _ => 0,
+            };
+        }
+        let total = (hour * 60 + minute + reading.later) % (24 * 60);
+        let (hour, minute) = (total / 60, total % 60);
+        match reading.format {
+            Format::Twelve => format!(
+                "{}:{minute:02}",
+                if hour % 12 == 0 { 12 } else { hour % 12 }
+            ),
+            Format::TwentyFour => format!("{hour}:{minute:02}"),
+        }
+    }
+
+    #[test]
+    fn hands_point_at_the_time() {
+        assert_eq!(hands(0, 0), (0.0, 0.0));
+        assert_eq!(hands(3, 0), (90.0, 0.0));
+        assert_eq!(hands(15, 30), (105.0, 180.0));
+        assert_eq!(hands(23, 45), (352.5, 270.0));
+    }
+
+    #[test]
+    fn hands_agree_with_the_answer() {
+        for hour in 0..24 {
+            for minute in 0..60 {
+                for format in [Format::Twelve, Format::TwentyFour] {
+                    for later in [0, 5, 30, 60] {
+                        let reading = Reading {
+                            hour,
+                            minute,
+                            format,
+                            later,
+                        };
+                        assert_eq!(read(&reading), reading.answer());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn answers() {
+        let answer = |hour, minute, format, later| {
+            Reading {
+                hour,
+                minute,
+                format,
+                later,
+            }
+            .answer()
+        };
+        assert_eq!(answer(0, 5, Format::Twelve, 0), "12:05");
+        assert_eq!(answer(0, 5, Format::TwentyFour, 0), "0:05");
+        assert_eq!(answer(12, 0, Format::Twelve, 0), "12:00");
+        assert_eq!(answer(15, 5, Format::Twelve, 0), "3:05");
+        assert_eq!(answer(15, 5, Format::TwentyFour, 0), "15:05");
+        assert_eq!(answer(9, 50, Format::TwentyFour, 20), "10:10");
+        // Past midnight
+        assert_eq!(answer(23, 50, Format::TwentyFour, 15), "0:05");
+        assert_eq!(answer(23, 50, Format::Twelve, 15), "12:05");
+        assert_eq!(answer(23, 0, Format::TwentyFour, 60), "0:00");
+    }
+
+    #[test]
+    fn periods() {
+        let period = |hour| {
+            Reading {
+                hour,
+                minute: 0,
+                format: Format::TwentyFour,
+                later: 0,
+            }
+            .period()
+        };
+        assert_eq!(period(2), "clock.night");
+        assert_eq!(period(9), "clock.morning");
+        assert_eq!(period(12), "clock.afternoon");
+        assert_eq!(period(20), "clock.evening");
+        assert_eq!(period(23), "clock.evening");
+    }
+}
@@ -0,0 +1,298 @@
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+use yew::{classes, function_component, html, use_context, use_state, Html, Properties};
+
+use crate::i18n::text;
+use crate::locale::Locale;
+use crate::settings::Settings;
+
+/// How far a colour written out in decimal may be from the box, to allow for rounding.
+const TOLERANCE: f64 = 4.0;
+/// How far a named colour may be from the box.
+const NAMED_TOLERANCE: f64 = 40.0;
+
+/// A 24-bit colour.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub fn random() -> Self {
+        let mut rng = thread_rng();
+        Self(rng.gen(), rng.gen(), rng.gen())
+    }
+
+    /// Parse six hexadecimal digits, or three shorthand ones.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|char| char.to_digit(16).and_then(|digit| u8::try_from(digit).ok()))
+            .collect::<Option<_>>()?;
+        match digits[..] {
+            [r1, r2, g1, g2, b1, b2] => Some(Self(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+            [r, g, b] => Some(Self(r * 17, g * 17, b * 17)),
+            _ => None,
+        }
+    }
+
+    /// Convert from a hue in degrees, and saturation and lightness between 0 and 1.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let channel = |offset: f64| {
+            let k = (offset + hue.rem_euclid(360.0) / 30.0) % 12.0;
+            let amount = saturation * lightness.min(1.0 - lightness);
+            let value = amount.mul_add(-(k - 3.0).min(9.0 - k).clamp(-1.0, 1.0), lightness);
+            (value * 255.0).round() as u8
+        };
+        Self(channel(0.0), channel(8.0), channel(4.0))
+    }
+
+    pub fn hex(self) -> String {
+        format!("{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+
+    /// Euclidean distance between two colours in RGB space.
+    pub fn distance(self, other: Self) -> f64 {
+        let square = |a: u8, b: u8| (f64::from(a) - f64::from(b)).powi(2);
+        (square(self.0, other.0) + square(self.1, other.1) + square(self.2, other.2)).sqrt()
+    }
+}
+
+/// The CSS named colours.
+fn named() -> impl Iterator<Item = (&'static str, Rgb)> {
+    include_str!("colours").lines().filter_map(|line| {
+        let (name, hex) = line.split_once(' ')?;
+        Some((name, Rgb::from_hex(hex)?))
+    })
+}
+
+/// Every run of six hexadecimal digits, e.g. `ffaa00`.
+fn parse_hex(text: &str) -> Vec<Rgb> {
+    let chars: Vec<_> = text.chars().collect();
+    chars
+        .windows(6)
+        .filter_map(|window| Rgb::from_hex(&window.iter().collect::<String>()))
+        .collect()
+}
+
+/// Every `#` followed by three hexadecimal digits, e.g. `#fa0`.
+fn parse_short(text: &str) -> Vec<Rgb> {
+    text.match_indices('#')
+        .filter_map(|(index, _)| text.get(index + 1..index + 4))
+        .filter_map(Rgb::from_hex)
+        .collect()
+}
+
+/// The arguments of every `function(a, b, c)` in the text, with any units dropped.
+fn arguments(text: &str, function: &str) -> Vec<[f64; 3]> {
+    let text = text.to_lowercase();
+    text.match_indices(&format!("{function}("))
+        .filter_map(|(index, prefix)| {
+            let (inside, _) = text[index + prefix.len()..].split_once(')')?;
+            inside
+                .split([',', ' '])
+                .filter(|argument| !argument.is_empty())
+                .map(|argument| {
+                    argument
+                        .trim_end_matches("deg")
+                        .trim_end_matches(['%', '\u{B0}'])
+                        .parse()
+                        .ok()
+                })
+                .collect::<Option<Vec<f64>>>()?
+                .try_into()
+                .ok()
+        })
+        .collect()
+}
+
+/// Every `rgb(r, g, b)`, e.g. `rgb(255, 170, 0)`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_rgb(text: &str) -> Vec<Rgb> {
+    let channel = |value: f64| value.clamp(0.0, 255.0).round() as u8;
+    arguments(text, "rgb")
+        .into_iter()
+        .map(|[r, g, b]| Rgb(channel(r), channel(g), channel(b)))
+        .collect()
+}
+
+/// Every `hsl(h, s%, l%)`, e.g. `hsl(40, 100%, 50%)`.
+fn parse_hsl(text: &str) -> Vec<Rgb> {
+    arguments(text, "hsl")
+        .into_iter()
+        .map(|[hue, saturation, lightness]| {
+            Rgb::from_hsl(hue, saturation / 100.0, lightness / 100.0)
+        })
+        .collect()
+}
+
+/// Every CSS colour name written as a word of its own, e.g. `orange`, so that neither "tan" in
+/// "standard" nor "red" in "darkred" counts.
+fn parse_named(text: &str) -> Vec<Rgb> {
+    let text = text.to_lowercase();
+    let is_letter = |char: Option<char>| char.is_some_and(char::is_alphabetic);
+    named()
+        .filter(|(name, _)| {
+            text.match_indices(name).any(|(start, _)| {
+                !is_letter(text[..start].chars().next_back())
+                    && !is_letter(text[start + name.len()..].chars().next())
+            })
+        })
+        .map(|(_, colour)| colour)
+        .collect()
+}
+
+/// The named colours nearest to a colour, of which there can be several when names share a
+/// value, e.g. `grey` and `gray`.
+fn nearest_named(colour: Rgb) -> Vec<Rgb> {
+    let nearest = named()
+        .map(|(_, named)| named.distance(colour))
+        .fold(f64::INFINITY, f64::min);
+    named()
+        .map(|(_, named)| named)
+        .filter(|named| named.distance(colour) <= nearest)
+        .collect()
+}
+
+/// A way of writing a colour down.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    Hex,
+    Short,
+    Rgb,
+    Hsl,
+    Named,
+}
+
+impl Notation {
+    pub const ALL: [Self; 5] = [Self::Hex, Self::Short, Self::Rgb, Self::Hsl, Self::Named];
+
+    /// Pick a notation, and a colour that can be written in it.
+    pub fn generate() -> (Self, Rgb) {
+        let notation = *Self::ALL.choose(&mut thread_rng()).unwrap();
+        let colour = match notation {
+            // Round every channel to a repeated digit
+            Self::Short => {
+                let Rgb(r, g, b) = Rgb::random();
+                Rgb(r / 17 * 17, g / 17 * 17, b / 17 * 17)
+            }
+            // Nudge a named colour, so it is close to the name but not exactly it
+            Self::Named => {
+                let (_, Rgb(r, g, b)) = *named()
+                    .collect::<Vec<_>>()
+                    .choose(&mut thread_rng())
+                    .unwrap();
+                let nudge =
+                    |channel: u8| channel.saturating_add_signed(thread_rng().gen_range(-8..=8));
+                Rgb(nudge(r), nudge(g), nudge(b))
+            }
+            Self::Hex | Self::Rgb | Self::Hsl => Rgb::random(),
+        };
+        (notation, colour)
+    }
+
+    /// Whether the text contains the colour written in this notation, near enough.
+    pub fn accepts(self, colour: Rgb, text: &str) -> bool {
+        let near = |found: Vec<Rgb>, tolerance: f64| {
+            found
+                .into_iter()
+                .any(|candidate| candidate.distance(colour) <= tolerance)
+        };
+        match self {
+            Self::Hex => near(parse_hex(text), 0.0),
+            Self::Short => near(parse_short(text), 0.0),
+            Self::Rgb => near(parse_rgb(text), TOLERANCE),
+            Self::Hsl => near(parse_hsl(text), TOLERANCE),
+            // Only the closest name counts, not just any that is near enough
+            Self::Named => {
+                let nearest = nearest_named(colour);
+                near(
+                    parse_named(text)
+                        .into_iter()
+                        .filter(|candidate| nearest.contains(candidate))
+                        .collect(),
+                    NAMED_TOLERANCE,
+                )
+            }
+        }
+    }
+
+    /// The catalogue key of the rule asking for this notation.
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Hex => "rule.hex-colour",
+            Self::Short => "rule.short-colour",
+            Self::Rgb => "rule.rgb-colour",
+            Self::Hsl => "rule.hsl-colour",
+            Self::Named => "rule.named-colour",
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ColourBoxProps {
+    /// The colour of the box, as six hexadecimal digits
+    pub hex: String,
+}
+
+/// A box filled with a colour. In colour-blind mode it comes with an eyedropper that reports the
+/// colour of wherever it is clicked.
+#[function_component]
+pub fn ColourBox(props: &ColourBoxProps) -> Html {
+    let locale = use_context::<Locale>().unwrap_or(Locale::English);
+    let settings = use_context::<Settings>().unwrap_or_default();
+    let sampling = use_state(|| false);
+    let sampled = use_state(|| None::<String>);
+    let pick = {
+        let sampling = sampling.clone();
+        move |_| sampling.set(!*sampling)
+    };
+    let sample = {
+        let sampling = sampling.clone();
+        let sampled = sampled.clone();
+        let hex = props.hex.clone();
+        move |_| {
+            if *sampling {
+                sampled.set(Some(hex.clone()));
+                sampling.set(false);
+            }
+        }
+    };
+
+    html! {
+        <div class="flex flex-col gap-4">
+            <div
+                role="img"
+                aria-label={text(locale, "colour.label", &[("hex", &props.hex)])}
+                onclick={sample}
+                class={classes!(
+                    "w-32", "h-32", "border-8", "border-slate-600",
+                    (*sampling).then_some("cursor-crosshair"),
+                )}
+                style={format!("background-color: #{}", props.hex)}
+            />
+            {
+                settings.colour_blind.then(|| html! {
+                    <div class="flex items-center gap-4">
+                        <button
+                            onclick={pick}
+                            aria-pressed={(*sampling).to_string()}
+                            class="p-2 transition bg-white border border-gray-700 rounded-xl hover:bg-gray-200"
+                        >
+                            {text(locale, "colour.eyedropper", &[])}
+                        </button>
+                        <span aria-live="polite">
+                            {
+                                (*sampled).as_ref().map(|hex| text(
+                                    locale,
+                                    "colour.sampled",
+                                    &[("hex", hex)],
+                                ))
+                            }
+                        </span>
+                    </div>
+                })
+            }
+        </div>
+    }
+}
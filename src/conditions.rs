@@ -1,86 +1,262 @@
-use std::iter::successors;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
 
-use chrono::Local;
+use crate::audio::MorsePlayer;
+use crate::autogram::Autogram;
+use crate::authenticator::{self, Authenticator, Secret};
+use crate::chess::{Chessboard, Position};
+use crate::checksum::Checksum;
+use crate::cipher::Cipher;
+use crate::clock::{AnalogClock, Reading};
+use crate::colour::{ColourBox, Notation};
+use crate::corpus::{Corpus, Quote};
+use crate::elements::tokenise;
+use crate::expression::{expressions, Ratio};
+use crate::i18n::{plural, text};
+use crate::living::{Fire, Pet};
+use crate::locale::Locale;
+use crate::maze::{Maze, MazeGrid};
+use crate::numerals::cardinal;
+use crate::pattern::Pattern;
+use crate::proof::ProofOfWork;
+use crate::registry::Registry;
+use crate::riddles::Riddle;
+use crate::roman::numerals;
+use crate::settings::Difficulty;
+use crate::ticker::{dollars, Ticker};
+use crate::tolerance::{Timekeeper, Tolerance};
+use crate::wordle::{colour, WordleGrid};
+use password::encoding::{from_braille, from_morse, from_nato, to_braille, to_morse, to_nato};
 use rand::thread_rng;
 use rand::{prelude::SliceRandom, Rng};
-use yew::virtual_dom::VNode;
-use yew::{classes, html, Html};
+use yew::{classes, html, virtual_dom::VNode, Html};
 
-pub type Condition = (Box<dyn Fn(&String, &String) -> bool>, Html);
-enum Colour {
-    Grey,
-    Yellow,
-    Green,
+pub struct Condition {
+    /// Whether the condition is met, given the username and password
+    pub check: Box<dyn Fn(&String, &String) -> bool>,
+    /// What to show the player, in their language
+    pub message: Box<dyn Fn(Locale) -> Html>,
+    /// The message as plain text for screen readers, when it's more than just text
+    pub summary: Option<Box<dyn Fn(Locale) -> String>>,
+    /// Extra detail about how the current username and password measure up, if any
+    pub feedback: Option<Box<dyn Fn(&String, &String, Locale) -> Html>>,
+    /// Advance the rule by a second, giving back a new password if the rule changes it
+    pub tick: Option<Box<dyn Fn(&String, &String) -> Option<String>>>,
+    /// Whether what the condition asks for changes with the time, so that freezing the clock
+    /// can't wait for it to be met
+    pub timed: bool,
+}
+
+impl Condition {
+    pub fn new(
+        check: impl Fn(&String, &String) -> bool + 'static,
+        message: impl Fn(Locale) -> Html + 'static,
+    ) -> Self {
+        Self {
+            check: Box::new(check),
+            message: Box::new(message),
+            summary: None,
+            feedback: None,
+            tick: None,
+            timed: false,
+        }
+    }
+
+    pub fn with_summary(mut self, summary: impl Fn(Locale) -> String + 'static) -> Self {
+        self.summary = Some(Box::new(summary));
+        self
+    }
+
+    pub fn with_feedback(
+        mut self,
+        feedback: impl Fn(&String, &String, Locale) -> Html + 'static,
+    ) -> Self {
+        self.feedback = Some(Box::new(feedback));
+        self
+    }
+
+    pub fn with_tick(
+        mut self,
+        tick: impl Fn(&String, &String) -> Option<String> + 'static,
+    ) -> Self {
+        self.tick = Some(Box::new(tick));
+        self
+    }
+
+    pub const fn timed(mut self) -> Self {
+        self.timed = true;
+        self
+    }
+
+    /// What the rule asks for as plain text, without any widgets that change as it's read.
+    pub fn summary(&self, locale: Locale) -> String {
+        if let Some(summary) = &self.summary {
+            return summary(locale);
+        }
+        match (self.message)(locale) {
+            VNode::VText(message) => message.text.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// The message, followed by any feedback on the current attempt.
+    pub fn render(&self, username: &String, password: &String, locale: Locale) -> Html {
+        html! {
+            <>
+                {(self.message)(locale)}
+                {self.feedback.as_ref().map(|feedback| feedback(username, password, locale))}
+            </>
+        }
+    }
+}
+
+/// The password with every copy of some content another rule forces on it blanked out, so that
+/// letters the player has no say over don't count towards a rule that scans for them.
+fn without(password: &str, forced: &[&str]) -> String {
+    forced
+        .iter()
+        .filter(|forced| !forced.is_empty())
+        .fold(password.to_string(), |password, forced| {
+            password.replace(forced, " ")
+        })
+}
+
+/// The sum of the atomic numbers of the elements in the password, apart from in content another
+/// rule forces on it.
+fn element_sum(password: &str, forced: &[&str]) -> usize {
+    tokenise(&without(password, forced))
+        .iter()
+        .filter_map(|(_, number)| *number)
+        .sum()
+}
+
+/// Advance every rule the player has reached by a second, giving back the password if any of them
+/// changed it. Living rules only come alive once the player has got as far as them, so rules past
+/// the first unmet one are left alone unless they were already discovered. The clock is then
+/// settled on whether the untimed rules, along with any others that have to hold too, are met.
+pub fn tick_reached(
+    conditions: &[Condition],
+    discovered: &[bool],
+    username: &String,
+    password: &String,
+    timekeeper: &Timekeeper,
+    others: &[Condition],
+) -> Option<String> {
+    let wrong_index = conditions
+        .iter()
+        .position(|condition| !(condition.check)(username, password));
+    let mut current = password.clone();
+    for (index, condition) in conditions.iter().enumerate() {
+        let reached = discovered.get(index).copied().unwrap_or(false)
+            || wrong_index.is_none_or(|wrong| index <= wrong);
+        if let Some(tick) = condition.tick.as_ref().filter(|_| reached) {
+            if let Some(changed) = tick(username, &current) {
+                current = changed;
+            }
+        }
+    }
+    timekeeper.settle(
+        untimed_met(conditions, username, &current) && untimed_met(others, username, &current),
+    );
+    (current != *password).then_some(current)
+}
+
+/// Whether every condition that doesn't depend on the time is met.
+fn untimed_met(conditions: &[Condition], username: &String, password: &String) -> bool {
+    conditions
+        .iter()
+        .filter(|condition| !condition.timed)
+        .all(|condition| (condition.check)(username, password))
 }
 
 #[allow(clippy::too_many_lines)]
-pub fn conditions() -> Vec<Condition> {
-    let numbers: Vec<_> = include_str!("numbers").trim().split('\n').collect();
+pub fn conditions(
+    registry: &Rc<Registry>,
+    locale: Locale,
+    difficulty: Difficulty,
+    timekeeper: &Rc<Timekeeper>,
+    secret: Secret,
+) -> Vec<Condition> {
+    let spell = |number: usize, locale| cardinal(number as i128, locale);
+    // The text to quote from is chosen once, since the ban on its country depends on it
+    let corpus = Corpus::choose(locale);
+    // The maze and chess puzzle are drawn up front too, since the letters of their answers would
+    // otherwise count as Roman numerals and elements
+    let maze = Rc::new(Maze::generate());
+    let solution = maze.solution();
+    let depth = Position::depth(difficulty);
+    let (position, answer) = Position::puzzle(depth);
+    // The check and mate signs are optional, as they only repeat what the rule says
+    let answer = answer.trim_end_matches(['#', '+']).to_string();
     let vec = vec![
-            (
-                Box::new(|_username: &String, password: &String| {
+            Condition::new(
+                |_username, password| {
                     !password.to_lowercase().contains("bean")
-                }) as Box<dyn Fn(&String, &String) -> bool>,
-                "Password may not contain the phrase 'bean'.".into(),
+                },
+                |locale| text(locale, "rule.bean", &[]).into(),
             ),
+            {
+                let registry = registry.clone();
+                Condition::new(
+                    move |_username, password| !registry.contains_password(password),
+                    |locale| text(locale, "rule.registry", &[]).into(),
+                )
+            },
             {
                 let number = thread_rng().gen_range(3..=6);
-                (
-                    Box::new(move |_username, password|
+                Condition::new(
+                    move |_username, password|
                         password
                             .chars()
-                            .filter(|char| char.is_uppercase()).count() >= number
-                    ),
-                    format!(
-                        "Password must contain at least {} uppercase characters.",
-                        numbers[number]
+                            .filter(|char| char.is_uppercase()).count() >= number,
+                    move |locale| plural(
+                        locale,
+                        "rule.uppercase",
+                        number,
+                        &[("count", &spell(number, locale))],
                     ).into(),
                 )
             },
             {
                 let number = thread_rng().gen_range(3..=6);
-                (
-                    Box::new(move |_username, password|
+                Condition::new(
+                    move |_username, password|
                         password
                             .chars()
-                            .filter(char::is_ascii_digit).count() >= number
-                    ),
-                    format!(
-                        "Password must contain at least {} digits.",
-                        numbers[number]
+                            .filter(char::is_ascii_digit).count() >= number,
+                    move |locale| plural(
+                        locale,
+                        "rule.digits",
+                        number,
+                        &[("count", &spell(number, locale))],
                     ).into(),
                 )
             },
-            (
-                Box::new(|_username, password| {
-                    include_str!("anthem")
-                        .trim()
-                        .split('\n')
-                        .any(|line| password.contains(line))
-                }),
-                "Password must contain a correctly punctuated line from the Australian national anthem."
-                    .into(),
-                ),
-                (
-                    Box::new(
-                        |_username, password|
-                            !password.contains("Australia")
-                    ),
-                    "Password may not contain the phrase 'Australia'.".into(),
+            {
+                let quote = Rc::new(Quote::generate(corpus));
+                Condition::new(
+                    {
+                        let quote = quote.clone();
+                        move |_username, password| quote.accepts(corpus, password)
+                    },
+                    move |locale| quote.message(corpus, locale).into(),
+                )
+            },
+                Condition::new(
+                    move |_username, password|
+                        !password.contains(corpus.country),
+                    move |locale| text(locale, "rule.country", &[("country", corpus.country)]).into(),
                 ),
-                (
-                    Box::new(
-                        |_username, password|
-                            password.contains('\u{1F6A1}')
-                    ),
-                    "Password must contain the aerial tramway emoji.".into(),
+                Condition::new(
+                    |_username, password|
+                        password.contains('\u{1F6A1}'),
+                    |locale| text(locale, "rule.tramway", &[]).into(),
                 ),
-                (
-                    Box::new(
-                        |_username, password|
-                            password.to_lowercase().contains('')
-                    ),
-                    "Password must contain the Apple logo.".into(),
+                Condition::new(
+                    |_username, password|
+                        password.to_lowercase().contains(''),
+                    |locale| text(locale, "rule.apple", &[]).into(),
                 ),
                 {
                 let mut words: Vec<&str> = include_str!("words").split('\n').collect();
@@ -88,357 +264,646 @@ pub fn conditions() -> Vec<Condition> {
                 let answer = *clone.choose(&mut thread_rng()).unwrap();
                 words.shuffle(&mut thread_rng());
                 let words = &words[..5];
-                let words = colour(words, answer);
-                (
-                    Box::new(
-                        move |_username, password|
-                            password.to_lowercase().contains(answer)
-                    ),
-                    html! {
+                let words = Rc::new(colour(words, answer));
+                Condition::new(
+                    move |_username, password|
+                        password.to_lowercase().contains(answer),
+                    move |locale| html! {
                         <div class="flex flex-col gap-4">
-                            <p>{"Password must contain the answer to this Wordle."}</p>
-                            <div class="flex">
-                                <div class="p-2 text-white bg-slate-600">
-                                    {
-                                        words.iter().map(|word| {
-                                            html! {
-                                                <div class="flex h-8">
-                                                    {word.iter().map(|(colour, character)| {
-                                                        match colour {
-                                                            Colour::Grey => html! {
-                                                                <div
-                                                                    class="grid w-8 bg-gray-500 // place-content-center"
-                                                                >
-                                                                    {character.to_string().to_uppercase()}
-                                                                </div>
-                                                            },
-                                                            Colour::Yellow => html! {
-                                                                <div
-                                                                    class="grid w-8 bg-yellow-500 // place-content-center"
-                                                                >
-                                                                    {character.to_string().to_uppercase()}
-                                                                </div>
-                                                            },
-                                                            Colour::Green => html! {
-                                                                <div
-                                                                    class="grid w-8 bg-green-500 // place-content-center"
-                                                                >
-                                                                    {character.to_string().to_uppercase()}
-                                                                </div>
-                                                            },
-                                                        }
-                                                    }).collect::<Html>()}
-                                                </div>
-                                            }
-                                        }).collect::<Html>()
-                                    }
-                                </div>
-                            </div>
+                            <p>{text(locale, "rule.wordle", &[])}</p>
+                            <WordleGrid guesses={words.clone()} />
                         </div>
                     }
                 )
+                .with_summary(|locale| text(locale, "rule.wordle", &[]))
             },
-            (
-                Box::new(
-                    |_username, password|
-                        password.contains(password.len().to_string().as_str())
-                ),
-                "Password must contain its length.".into(),
+            Condition::new(
+                |_username, password|
+                    password.contains(password.len().to_string().as_str()),
+                |locale| text(locale, "rule.length", &[]).into(),
             ),
-            (
-                Box::new(|username, password| password.contains(&username.chars().rev().collect::<String>())),
-                "Password must contain the username reversed.".into(),
+            {
+                let autogram = Autogram::generate();
+                Condition::new(
+                    move |_username, password| autogram.accepts(password),
+                    move |locale| autogram.message(locale).into(),
+                )
+                .with_feedback(move |_username, password, locale| html! {
+                    <span class="block text-sm">{autogram.feedback(password, locale)}</span>
+                })
+            },
+            Condition::new(
+                |username, password| password.contains(&username.chars().rev().collect::<String>()),
+                |locale| text(locale, "rule.username-reversed", &[]).into(),
             ),
             {
                 let number = thread_rng().gen_range(58..=68);
-                (
-                    Box::new(move |_username, password| {
+                Condition::new(
+                    move |_username, password| {
                         password
                             .chars()
                             .filter_map(|char| char.to_string().parse::<usize>().ok())
                             .sum::<usize>()
                             == number
-                    }),
-                    format!("Digits in password must sum to {}.", numbers[number]).into(),
+                    },
+                    move |locale| text(locale, "rule.digit-sum", &[("sum", &spell(number, locale))]).into(),
                 )
             },
-                        (
-                Box::new(
-                    |_username, password|
-                        password.to_lowercase().contains("blue")
-                ),
-                "Password must contain my favourite colour.".into(),
-            ),
             {
-                let [r, g, b] = (0..3)
-                    .map(|_| thread_rng().gen_range(0..=0xff))
-                    .collect::<Vec<_>>()
-                    .try_into()
-                    .unwrap();
-                let hex = format!("{r:02x}{g:02x}{b:02x}");
-                (
+                let checksum = Checksum::generate();
+                let condition = Condition::new(
+                    move |_username, password| checksum.accepts(password),
+                    move |locale| text(locale, checksum.key(), &[]).into(),
+                );
+                // Only hard games get told what the check currently works out to
+                if difficulty == Difficulty::Hard {
+                    condition.with_feedback(move |_username, password, locale| html! {
+                        <span class="block text-sm">{checksum.hint(password, locale)}</span>
+                    })
+                } else {
+                    condition
+                }
+            },
+            {
+                let proof = Rc::new(ProofOfWork::generate(difficulty));
+                Condition::new(
+                    {
+                        let proof = proof.clone();
+                        move |_username, password| proof.accepts(password)
+                    },
+                    {
+                        let proof = proof.clone();
+                        move |locale| text(locale, "rule.proof", &[("prefix", &proof.prefix)]).into()
+                    },
+                )
+                .with_feedback(move |_username, password, locale| html! {
+                    <span class="block text-sm">
+                        {text(locale, "proof.current", &[])}
+                        <span class="block font-mono break-all">{proof.hash(password)}</span>
+                    </span>
+                })
+            },
+            {
+                let number = thread_rng().gen_range(100..=999);
+                Condition::new(
+                    move |_username, password| {
+                        expressions(password)
+                            .iter()
+                            .any(|(_, value)| *value == Ratio::integer(number as i128))
+                    },
+                    move |locale| text(
+                        locale,
+                        "rule.expression",
+                        &[("number", &spell(number, locale))],
+                    ).into(),
+                )
+                .with_feedback(|_username, password, locale| {
+                    let found = expressions(password);
+                    if found.is_empty() {
+                        return html! {
+                            <span class="block text-sm">{text(locale, "expression.none", &[])}</span>
+                        };
+                    }
+                    found.iter().map(|(expression, value)| html! {
+                        <span class="block font-mono text-sm">{format!("{expression} = {value}")}</span>
+                    }).collect()
+                })
+            },
+            {
+                // Build the target from numerals that can be written, so it can be reached
+                let number: u32 = (0..2).map(|_| thread_rng().gen_range(2..=30)).product();
+                let forced = solution.clone();
+                Condition::new(
+                    {
+                        let forced = forced.clone();
+                        move |_username, password| {
+                            let found = numerals(&without(password, &[&forced]));
+                            !found.is_empty()
+                                && found
+                                    .iter()
+                                    .filter_map(|(_, value)| *value)
+                                    .map(u64::from)
+                                    // Many long numerals can overflow, but never to the target
+                                    .fold(1, u64::saturating_mul)
+                                    == u64::from(number)
+                        }
+                    },
+                    move |locale| text(
+                        locale,
+                        "rule.roman",
+                        &[("number", &spell(number as usize, locale))],
+                    ).into(),
+                )
+                .with_feedback(move |_username, password, locale| {
+                    let found = numerals(&without(password, &[&forced]));
+                    if found.is_empty() {
+                        return html! {
+                            <span class="block text-sm">{text(locale, "roman.none", &[])}</span>
+                        };
+                    }
+                    let product = found
+                        .iter()
+                        .filter_map(|(_, value)| *value)
+                        .map(u64::from)
+                        .fold(1, u64::saturating_mul);
+                    html! {
+                        <>
+                            {
+                                found.iter().map(|(numeral, value)| html! {
+                                    <span class="block font-mono text-sm">
+                                        {
+                                            value.map_or_else(
+                                                || text(locale, "roman.invalid", &[("numeral", numeral)]),
+                                                |value| format!("{numeral} = {value}"),
+                                            )
+                                        }
+                                    </span>
+                                }).collect::<Html>()
+                            }
+                            <span class="block text-sm">
+                                {text(locale, "roman.product", &[("product", &product.to_string())])}
+                            </span>
+                        </>
+                    }
+                })
+            },
+            {
+                let number = thread_rng().gen_range(100..=250);
+                let forced = [solution.clone(), answer.clone()];
+                Condition::new(
                     {
-                        let hex = hex.clone();
-                        Box::new(
-                            move |_username, password|
-                                password.to_lowercase().contains(&hex)
-                        )
+                        let forced = forced.clone();
+                        move |_username, password| {
+                            element_sum(password, &[&forced[0], &forced[1]]) == number
+                        }
                     },
+                    move |locale| text(
+                        locale,
+                        "rule.elements",
+                        &[("number", &spell(number, locale))],
+                    ).into(),
+                )
+                .with_feedback(move |_username, password, locale| {
+                    let tokens = tokenise(&without(password, &[&forced[0], &forced[1]]));
+                    if tokens.is_empty() {
+                        return html! {
+                            <span class="block text-sm">{text(locale, "elements.none", &[])}</span>
+                        };
+                    }
+                    let sum: usize = tokens.iter().filter_map(|(_, number)| *number).sum();
                     html! {
+                        <>
+                            <span class="block font-mono text-sm">
+                                {
+                                    tokens.iter().map(|(symbol, number)| number.map_or_else(
+                                        || format!("{symbol}(?)"),
+                                        |number| format!("{symbol}({number})"),
+                                    )).collect::<Vec<_>>().join(" + ")
+                                }
+                            </span>
+                            <span class="block text-sm">
+                                {text(locale, "elements.sum", &[("sum", &sum.to_string())])}
+                            </span>
+                        </>
+                    }
+                })
+            },
+            Condition::new(
+                |_username, password| {
+                    let password = password.to_lowercase();
+                    // Accept my favourite colour in any of the game's languages
+                    password.contains("blue") || password.contains("blau")
+                },
+                |locale| text(locale, "rule.favourite-colour", &[]).into(),
+            ),
+            {
+                let (notation, colour) = Notation::generate();
+                Condition::new(
+                    move |_username, password| notation.accepts(colour, password),
+                    move |locale| html! {
                         <div class="flex flex-col gap-4">
-                            <p>{"Password must contain the 24-bit hexadecimal colour of this box."}</p>
-                            <div
-                                class="w-32 h-32 border-8 border-slate-600"
-                                style={format!("background-color: #{hex}")}
-                            />
+                            <p>{text(locale, notation.key(), &[])}</p>
+                            <ColourBox hex={colour.hex()} />
                         </div>
                     },
                 )
+                .with_summary(move |locale| text(locale, notation.key(), &[]))
             },
             {
                 let number = thread_rng().gen_range(46..=58);
-                (
-                    Box::new(move |_username, password|
+                Condition::new(
+                    move |_username, password|
                         password
                         .chars()
-                        .filter(|char| char.is_lowercase()).count() == number
-                    ),
-                    format!(
-                        "Password must contain exactly {} lowercase characters.",
-                        numbers[number]
+                        .filter(|char| char.is_lowercase()).count() == number,
+                    move |locale| plural(
+                        locale,
+                        "rule.lowercase",
+                        number,
+                        &[("count", &spell(number, locale))],
                     ).into(),
                 )
             },
             {
-                let (riddle, answer) = [
-                    ("What do you call a person that's struggling to set a password? An _____.", "idiot")
-                    ].choose(&mut thread_rng()).unwrap();
-                    (
-                        Box::new(
-                            move |_username, password|
-                            password.to_lowercase().contains(answer)
-                        ),
-                        html! {
+                let riddle = Rc::new(Riddle::choose(difficulty));
+                Condition::new(
+                    {
+                        let riddle = riddle.clone();
+                        move |_username, password| riddle.accepts(password)
+                    },
+                    {
+                        let riddle = riddle.clone();
+                        move |locale| html! {
                             <div class="flex flex-col gap-4">
-                            <p>{"Password must contain the answer to this riddle:"}</p>
-                            <p>{*riddle}</p>
+                                <p>{text(locale, "rule.riddle", &[])}</p>
+                                <p class="p-4 text-white bg-slate-600 rounded-xl">
+                                    {text(locale, &riddle.key, &[])}
+                                </p>
                             </div>
                         }
+                    },
+                )
+                .with_summary(move |locale| {
+                    format!(
+                        "{} {}",
+                        text(locale, "rule.riddle", &[]),
+                        text(locale, &riddle.key, &[]),
                     )
+                })
+            },
+            {
+                let words: Vec<_> = include_str!("words").lines().collect();
+                let plaintext = *words.choose(&mut thread_rng()).unwrap();
+                let cipher = Rc::new(Cipher::generate(difficulty));
+                let ciphertext = cipher.encipher(plaintext);
+                Condition::new(
+                    move |_username, password| password.to_lowercase().contains(plaintext),
+                    {
+                        let cipher = cipher.clone();
+                        move |locale| html! {
+                            <div class="flex flex-col gap-4">
+                                <p>{text(locale, "rule.cipher", &[])}</p>
+                                <p>{cipher.describe(locale)}</p>
+                                <p class="p-4 font-mono text-lg tracking-widest text-white uppercase bg-slate-600 rounded-xl">
+                                    {ciphertext.clone()}
+                                </p>
+                            </div>
+                        }
+                    },
+                )
+                .with_summary(move |locale| {
+                    format!(
+                        "{} {}",
+                        text(locale, "rule.cipher", &[]),
+                        cipher.describe(locale),
+                    )
+                })
             },
-            (
-                Box::new(|_username, password| ('\u{1F3FB}'..='\u{1F3FF}').all(|char| password.contains(char))),
-                "Password must be ethnically diverse. 👍".into()
-            ),
             {
-                    const MAZE_SIZE: u32 = 20;
-                    const BORDER_WIDTH: u32 = 1;
-                    type Cell = (Position, bool);
-                    type Position = (u32, u32);
-                    fn neighbours((x, y): Position) -> Vec<Position> {
-                        vec![
-                            (x, y.wrapping_sub(1)),
-                            (x.wrapping_add(1), y),
-                            (x, y.wrapping_add(1)),
-                            (x.wrapping_sub(1), y),
-                            ]
+                let words: Vec<_> = include_str!("words").lines().collect();
+                let word = *words.choose(&mut thread_rng()).unwrap();
+                let encodings: [(&str, &str, fn(&str) -> String, fn(&str) -> Vec<String>); 3] = [
+                    ("rule.morse-decode", "rule.morse-encode", to_morse, from_morse),
+                    ("rule.nato-decode", "rule.nato-encode", to_nato, from_nato),
+                    ("rule.braille-decode", "rule.braille-encode", to_braille, from_braille),
+                ];
+                let (decode_key, encode_key, encode, decode) =
+                    *encodings.choose(&mut thread_rng()).unwrap();
+                let encoded = encode(word);
+                // Either show the encoded word and ask for it decoded, or the other way round
+                let decoding = thread_rng().gen_bool(0.5);
+                Condition::new(
+                    move |_username, password| {
+                        if decoding {
+                            password.to_lowercase().contains(word)
+                        } else {
+                            decode(password).iter().any(|found| found.contains(word))
                         }
-                        #[repr(u8)]
-                        enum Direction {
-                            Up = b'R',
-                            Right = b'D',
-                            Down = b'L',
-                            Left = b'U',
+                    },
+                    move |locale| if decoding {
+                        html! {
+                            <div class="flex flex-col gap-4">
+                                <p>{text(locale, decode_key, &[])}</p>
+                                <p class="p-4 font-mono text-2xl text-white bg-slate-600 rounded-xl">
+                                    {encoded.clone()}
+                                </p>
+                                {
+                                    (decode_key == "rule.morse-decode").then(|| html! {
+                                        <MorsePlayer code={encoded.clone()} />
+                                    })
+                                }
+                            </div>
                         }
-
-                        let mut maze: Vec<_> = (0..MAZE_SIZE)
-                        .flat_map(|y| (0..MAZE_SIZE).map(move |x| ((x, y), false) as Cell))
-                        .collect();
-                    let mut stack = Vec::new();
-                    let mut paths = Vec::new();
-                    let cell = (0, 0);
-                    let (_, visited) = maze
-                    .iter_mut()
-                    .find(|(position, _)| *position == cell)
-                    .unwrap();
-                *visited = true;
-                stack.push(cell);
-                while let Some(cell) = stack.pop() {
-                    let neighbours: Vec<_> = neighbours(cell)
-                    .into_iter()
-                    .filter_map(|neighbour| {
-                        maze.iter().find_map(|(position, visited)| {
-                            (*position == neighbour && !visited).then_some(*position)
-                        })
-                    })
-                    .collect();
-                if let Some(neighbour) = neighbours.choose(&mut thread_rng()) {
-                    stack.push(cell);
-                    paths.push((cell, *neighbour));
-                    let (_, visited) = maze
-                    .iter_mut()
-                    .find(|(position, _)| *position == *neighbour)
-                    .unwrap();
-                *visited = true;
-                stack.push(*neighbour);
-            }
-        }
-        let (solution, start, goal) = {
-            let maze: Vec<_> = maze.iter().map(|(position, _)| position).copied().collect();
-            let cell = *maze.choose(&mut thread_rng()).unwrap();
-            let goal = *maze.choose(&mut thread_rng()).unwrap();
-            let mut queue = vec![cell];
-            let mut explored = vec![cell];
-            let mut links = Vec::new();
-            while let Some(cell) = queue.pop() {
-                if cell == goal {
-                    break;
-                }
-                for neighbour in neighbours(cell) {
-                    if maze.contains(&neighbour)
-                    && !explored.contains(&neighbour)
-                    && paths.iter().any(|(a, b)| {
-                        *a == cell && *b == neighbour || *a == neighbour && *b == cell
-                    })
+                    } else {
+                        text(locale, encode_key, &[("word", word)]).into()
+                    },
+                )
+                .with_summary(move |locale| if decoding {
+                    text(locale, decode_key, &[])
+                } else {
+                    text(locale, encode_key, &[("word", word)])
+                })
+            },
+            {
+                let pattern = Rc::new(Pattern::generate(difficulty));
+                Condition::new(
+                    {
+                        let pattern = pattern.clone();
+                        move |_username, password| pattern.find(password).is_some()
+                    },
                     {
-                        queue.push(neighbour);
-                        links.push((neighbour, cell));
-                        explored.push(neighbour);
+                        let pattern = pattern.clone();
+                        move |locale| html! {
+                            <div class="flex flex-col gap-4">
+                                <p>{text(locale, "rule.pattern", &[])}</p>
+                                <p class="p-4 font-mono text-lg text-white bg-slate-600 rounded-xl">
+                                    {pattern.to_string()}
+                                </p>
+                            </div>
+                        }
+                    },
+                )
+                .with_summary({
+                    let pattern = pattern.clone();
+                    move |locale| format!("{} {pattern}", text(locale, "rule.pattern", &[]))
+                })
+                .with_feedback(move |_username, password, locale| {
+                    let feedback = pattern.closest(password).map_or_else(
+                        || text(locale, "pattern.none", &[]),
+                        |(closest, parts)| text(
+                            locale,
+                            "pattern.closest",
+                            &[
+                                ("match", &closest),
+                                ("count", &parts.to_string()),
+                                ("total", &pattern.part_count().to_string()),
+                            ],
+                        ),
+                    );
+                    html! {
+                        <span class="block text-sm">{feedback}</span>
                     }
-                }
-            }
-            let path: Vec<_> = successors(Some(goal), move |&cell| {
-                links
-                .iter()
-                .find_map(|(to, from)| (*to == cell).then_some(*from))
-            })
-            .collect();
-        let path: Vec<_> = path.iter().copied().rev().collect();
-        (path, cell, goal)
-    };
-    let solution: String = solution
-    .windows(2)
-    .map(|window| {
-        let [from, to] = window else { unreachable!() };
-        (if from.0 < to.0 {
-            Direction::Right
-        } else if from.0 > to.0 {
-            Direction::Left
-        } else if from.1 < to.1 {
-            Direction::Down
-        } else {
-            Direction::Up
-        }) as u8 as char
-    })
-    .collect();
-    let maze = (0..MAZE_SIZE)
-    .flat_map(|y| {
-        let paths = paths.clone();
-        (0..MAZE_SIZE).map(move |x| {
-            let neighbours: Vec<_> = neighbours((x, y))
-            .iter()
-            .map(|neighbour| {
-                format!(
-                    "{}px",
-                    if paths.iter().any(|(from, to)| {
-                        (*from == (x, y) && *to == *neighbour)
-                        || (*from == *neighbour && *to == (x, y))
-                    }) {
-                        0
-                    } else {
-                        BORDER_WIDTH
+                })
+            },
+            Condition::new(
+                |_username, password| ('\u{1F3FB}'..='\u{1F3FF}').all(|char| password.contains(char)),
+                |locale| text(locale, "rule.diverse", &[]).into()
+            ),
+            {
+                let pet = Rc::new(RefCell::new(Pet::new(difficulty)));
+                Condition::new(
+                    {
+                        let pet = pet.clone();
+                        move |_username, password| pet.borrow().accepts(password)
+                    },
+                    |locale| text(locale, "rule.pet", &[]).into(),
+                )
+                .with_feedback({
+                    let pet = pet.clone();
+                    move |_username, _password, locale| {
+                        let pet = pet.borrow();
+                        html! {
+                            <span class="block text-sm">
+                                {
+                                    if pet.starved {
+                                        text(locale, "pet.starved", &[])
+                                    } else {
+                                        text(locale, "pet.next", &[("seconds", &pet.remaining().to_string())])
+                                    }
+                                }
+                            </span>
+                        }
                     }
+                })
+                .with_tick(move |_username, password| pet.borrow_mut().tick(password))
+            },
+            {
+                let fire = RefCell::new(Fire::default());
+                Condition::new(
+                    |_username, password| Fire::accepts(password),
+                    |locale| text(locale, "rule.fire", &[]).into(),
                 )
-            })
-            .collect();
-        let borders = neighbours.join(" ");
-        html! {
-            <div
-            style={format!("border-width: {borders}")}
-            class={classes!(
-                "border-white", "size-full",
-                ((x, y) == start).then_some("bg-green-500"),
-                ((x, y) == goal).then_some("bg-red-500"),
-            )}
-            />
-        }
-    })
-    })
-    .collect::<VNode>();
-    (
-        Box::new(move |_username, password| password.contains(&solution)),
-        html! {
-            <div>
-            <p>{"Password must contain the optimal solution to this maze, from green to red."}</p>
-            <p>{"R is up, D is right, L is down, U is left."}</p>
-            <div class="p-4 aspect-square bg-slate-600">
-            <div class="grid grid-cols-[repeat(20,minmax(0,1fr))] size-full">
-            {maze}
-            </div>
-            </div>
-            </div>
-        },
-    )
+                .with_tick(move |_username, password| fire.borrow_mut().tick(password))
+            },
+            {
+                // Each second of grace keeps one more price, on top of the few the ticker always
+                // keeps
+                let grace = match timekeeper.tolerance {
+                    Tolerance::Grace(seconds) => usize::try_from(seconds).unwrap_or(0) + 1,
+                    Tolerance::Strict | Tolerance::Freeze => 0,
+                };
+                let ticker = Rc::new(RefCell::new(Ticker::generate(grace)));
+                let timekeeper = timekeeper.clone();
+                Condition::new(
+                    {
+                        let ticker = ticker.clone();
+                        move |_username, password| ticker.borrow().accepts(password)
+                    },
+                    {
+                        let ticker = ticker.clone();
+                        move |locale| {
+                            let ticker = ticker.borrow();
+                            html! {
+                                <div class="flex flex-col gap-4">
+                                    <p>{text(locale, "rule.ticker", &[])}</p>
+                                    <div class="flex items-center justify-between p-4 text-white bg-slate-600 rounded-xl">
+                                        <span class="font-mono text-xl">{ticker.symbol}</span>
+                                        <span class={classes!(
+                                            "font-mono", "text-3xl",
+                                            if ticker.rising() { "text-green-400" } else { "text-red-400" },
+                                        )}>
+                                            {if ticker.rising() { "\u{25B2} " } else { "\u{25BC} " }}
+                                            {dollars(ticker.price())}
+                                        </span>
+                                    </div>
+                                </div>
+                            }
+                        }
+                    },
+                )
+                // The price changes every second, so it's left out of what's read aloud
+                .with_summary(|locale| text(locale, "rule.ticker", &[]))
+                .with_tick(move |_username, _password| {
+                    if !timekeeper.frozen() {
+                        ticker.borrow_mut().tick();
+                    }
+                    None
+                })
+                .timed()
+            },
+            Condition::new(
+                move |_username, password| password.contains(&solution),
+                move |locale| html! {
+                    <div>
+                        <p>{text(locale, "rule.maze", &[])}</p>
+                        <p>{text(locale, "rule.maze-directions", &[])}</p>
+                        <MazeGrid maze={maze.clone()} />
+                    </div>
+                },
+            )
+            .with_summary(|locale| {
+                format!(
+                    "{} {}",
+                    text(locale, "rule.maze", &[]),
+                    text(locale, "rule.maze-directions", &[]),
+                )
+            }),
+            {
+                let position = Rc::new(position);
+                Condition::new(
+                    move |_username, password| password.contains(&answer),
+                    move |locale| html! {
+                        <div class="flex flex-col gap-4">
+                            <p>{text(locale, if depth > 1 { "rule.chess-two" } else { "rule.chess" }, &[])}</p>
+                            <Chessboard position={position.clone()} />
+                        </div>
+                    },
+                )
+                .with_summary(move |locale| {
+                    text(locale, if depth > 1 { "rule.chess-two" } else { "rule.chess" }, &[])
+                })
+            },
+    {
+        // The same secret as the two-factor stage, so the player sets up one authenticator
+        let timekeeper = timekeeper.clone();
+        Condition::new(
+            move |_username, password| {
+                timekeeper.moments().into_iter()
+                    .any(|moment| password.contains(&authenticator::code(&secret, moment)))
+            },
+            move |locale| html! {
+                <div class="flex flex-col gap-4">
+                    <p>{text(locale, "rule.authenticator", &[])}</p>
+                    <Authenticator secret={secret} />
+                </div>
+            },
+        )
+        .with_summary(|locale| text(locale, "rule.authenticator", &[]))
+        .timed()
     },
-    (
-        Box::new(
-            |_username, password|
-                password.contains(&Local::now().format("%-H:%M").to_string())
-        ),
-        "Password must contain the current time in the format HH:MM.".into(),
-    ),
-                (
-                Box::new(
+    {
+        let timekeeper = timekeeper.clone();
+        Condition::new(
+            move |_username, password| {
+                timekeeper.moments().into_iter()
+                    .any(|moment| password.contains(&moment.format("%-H:%M").to_string()))
+            },
+            |locale| text(locale, "rule.time", &[]).into(),
+        )
+        .timed()
+    },
+    {
+        let reading = Rc::new(Reading::generate());
+        let answer = reading.answer();
+        Condition::new(
+            move |_username, password| password.contains(&answer),
+            {
+                let reading = reading.clone();
+                move |locale| html! {
+                    <div class="flex flex-col gap-4">
+                        <p>{reading.message(locale)}</p>
+                        <AnalogClock hour={reading.hour} minute={reading.minute} />
+                    </div>
+                }
+            },
+        )
+        .with_summary(move |locale| reading.message(locale))
+    },
+                Condition::new(
                     |_username, password|
-                    *password == password.chars().rev().collect::<String>()
-                ),
-                "Password must be a palindrome.".into(),
+                    *password == password.chars().rev().collect::<String>(),
+                    |locale| text(locale, "rule.palindrome", &[]).into(),
             ),
     ];
     vec
 }
 
-fn colour(words: &[&str], answer: &str) -> Vec<Vec<(Colour, char)>> {
-    words
-        .iter()
-        .map(|word| {
-            word.chars()
-                .enumerate()
-                .map(|(index, character)| {
-                    (
-                        'colour: {
-                            if character == answer.chars().nth(index).unwrap() {
-                                break 'colour Colour::Green;
-                            }
-                            let mut wrong_word = 0;
-                            let mut wrong_guess = 0;
-                            for (answer_index, answer_character) in answer.chars().enumerate() {
-                                if answer_character == character
-                                    && word.chars().nth(answer_index).unwrap() != character
-                                {
-                                    wrong_word += 1;
-                                }
-                                if (answer_index <= index)
-                                    && (word.chars().nth(answer_index).unwrap() == character
-                                        && answer_character != character)
-                                {
-                                    wrong_guess += 1;
-                                }
-                                if answer_index >= index {
-                                    if wrong_guess == 0 {
-                                        break;
-                                    }
-                                    if wrong_guess <= wrong_word {
-                                        break 'colour Colour::Yellow;
-                                    }
-                                }
-                            }
-                            Colour::Grey
-                        },
-                        character,
+/// Rules that apply to the username rather than the password.
+pub fn username_conditions(registry: &Rc<Registry>) -> Vec<Condition> {
+    let spell = |number: usize, locale| cardinal(number as i128, locale);
+    vec![
+        {
+            let number = thread_rng().gen_range(6..=9);
+            Condition::new(
+                move |username, _password| username.chars().count() >= number,
+                move |locale| {
+                    plural(
+                        locale,
+                        "username.length",
+                        number,
+                        &[("count", &spell(number, locale))],
                     )
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect()
+                    .into()
+                },
+            )
+        },
+        Condition::new(
+            |username, _password| !username.chars().any(char::is_whitespace),
+            |locale| text(locale, "username.spaces", &[]).into(),
+        ),
+        {
+            let classes: [(&str, fn(char) -> bool); 3] = [
+                ("class.digit", |char| char.is_ascii_digit()),
+                ("class.uppercase", char::is_uppercase),
+                ("class.symbol", |char| char.is_ascii_punctuation()),
+            ];
+            let (name, class) = *classes.choose(&mut thread_rng()).unwrap();
+            Condition::new(
+                move |username, _password| username.chars().any(class),
+                move |locale| {
+                    text(locale, "username.class", &[("class", &text(locale, name, &[]))]).into()
+                },
+            )
+        },
+        {
+            // Sort the letters of every word so that anagrams compare equal
+            let words: HashSet<String> = include_str!("words")
+                .split('\n')
+                .map(|word| sorted(word.chars()))
+                .collect();
+            Condition::new(
+                move |username, _password| {
+                    words.contains(&sorted(
+                        username
+                            .chars()
+                            .filter(char::is_ascii_alphabetic)
+                            .map(|char| char.to_ascii_lowercase()),
+                    ))
+                },
+                |locale| text(locale, "username.anagram", &[]).into(),
+            )
+        },
+        {
+            let registry = registry.clone();
+            Condition::new(
+                move |username, _password| !registry.contains_username(username),
+                |locale| text(locale, "username.taken", &[]).into(),
+            )
+        },
+    ]
+}
+
+fn sorted(chars: impl Iterator<Item = char>) -> String {
+    let mut chars: Vec<_> = chars.collect();
+    chars.sort_unstable();
+    chars.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forced_answers_are_not_elements() {
+        for _ in 0..50 {
+            let solution = Maze::generate().solution();
+            // Only the uranium and iron the player chose count, not the letters of the answers
+            let password = format!("U {solution} Ra8 Fe");
+            assert_eq!(element_sum(&password, &[&solution, "Ra8"]), 92 + 26);
+            assert_eq!(
+                element_sum(&password, &[]),
+                92 + 26 + 88 + 92 * solution.matches('U').count(),
+            );
+        }
+    }
 }
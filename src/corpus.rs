@@ -0,0 +1,177 @@
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+
+use crate::i18n::text;
+use crate::locale::Locale;
+use crate::numerals::ordinal;
+
+/// A body of text, such as a national anthem, that players have to quote from.
+pub struct Corpus {
+    /// Catalogue key for the name of the text, e.g. "the Australian national anthem"
+    pub name: &'static str,
+    /// The country the text belongs to, which players may not mention
+    pub country: &'static str,
+    /// The language the text is written in
+    pub locale: Locale,
+    pub text: &'static str,
+    /// Pairs of (zero-based) line numbers whose endings rhyme. Rhyming is transitive, so each
+    /// pair only needs to join a line to one other line of its rhyme.
+    pub rhymes: &'static [(usize, usize)],
+}
+
+pub static CORPORA: [Corpus; 4] = [
+    Corpus {
+        name: "corpus.australia",
+        country: "Australia",
+        locale: Locale::English,
+        text: include_str!("corpora/australia"),
+        rhymes: &[(1, 2), (1, 4), (6, 8), (8, 15), (11, 13), (15, 17)],
+    },
+    Corpus {
+        name: "corpus.united-kingdom",
+        country: "Britain",
+        locale: Locale::English,
+        text: include_str!("corpora/united-kingdom"),
+        rhymes: &[(3, 4)],
+    },
+    Corpus {
+        name: "corpus.united-states",
+        country: "America",
+        locale: Locale::English,
+        text: include_str!("corpora/united-states"),
+        rhymes: &[(0, 2), (1, 3), (4, 5), (6, 7)],
+    },
+    Corpus {
+        name: "corpus.germany",
+        country: "deutsch",
+        locale: Locale::German,
+        text: include_str!("corpora/germany"),
+        rhymes: &[(1, 3), (3, 5), (5, 7)],
+    },
+];
+
+impl Corpus {
+    /// Pick a text in the player's language half of the time, and any text otherwise.
+    pub fn choose(locale: Locale) -> &'static Self {
+        let local: Vec<_> = CORPORA
+            .iter()
+            .filter(|corpus| corpus.locale == locale)
+            .collect();
+        if !local.is_empty() && thread_rng().gen_bool(0.5) {
+            local.choose(&mut thread_rng()).unwrap()
+        } else {
+            CORPORA.choose(&mut thread_rng()).unwrap()
+        }
+    }
+
+    pub fn lines(&self) -> Vec<&'static str> {
+        self.text.trim().split('\n').collect()
+    }
+
+    /// Every line that rhymes with the given line, including the line itself, found by
+    /// following the pairs from line to line.
+    fn rhyme_class(&self, line: usize) -> Vec<usize> {
+        let mut class = vec![line];
+        let mut index = 0;
+        while let Some(&current) = class.get(index) {
+            for &(a, b) in self.rhymes {
+                let other = if a == current {
+                    b
+                } else if b == current {
+                    a
+                } else {
+                    continue;
+                };
+                if !class.contains(&other) {
+                    class.push(other);
+                }
+            }
+            index += 1;
+        }
+        class.sort_unstable();
+        class
+    }
+}
+
+/// What a player has to quote from a corpus.
+pub enum Quote {
+    /// Any line at all
+    AnyLine,
+    /// One particular (zero-based) line
+    Line(usize),
+    /// Any line in the rhyme of the given word
+    Rhyme { word: &'static str, lines: Vec<usize> },
+}
+
+impl Quote {
+    /// Pick a quote that can be satisfied without mentioning the corpus's country.
+    pub fn generate(corpus: &Corpus) -> Self {
+        let lines = corpus.lines();
+        let allowed: Vec<_> = (0..lines.len())
+            .filter(|&index| !lines[index].contains(corpus.country))
+            .collect();
+        let mut rhymes: Vec<_> = (0..lines.len())
+            .filter_map(|index| {
+                let class = corpus.rhyme_class(index);
+                let partners: Vec<_> = class
+                    .iter()
+                    .copied()
+                    .filter(|partner| allowed.contains(partner))
+                    .collect();
+                // A line on its own has nothing to rhyme with
+                (class.len() > 1 && !partners.is_empty()).then(|| Self::Rhyme {
+                    word: last_word(lines[index]),
+                    lines: partners,
+                })
+            })
+            .collect();
+        match thread_rng().gen_range(0..3) {
+            1 => allowed
+                .choose(&mut thread_rng())
+                .map_or(Self::AnyLine, |&line| Self::Line(line)),
+            2 if !rhymes.is_empty() => {
+                let index = thread_rng().gen_range(0..rhymes.len());
+                rhymes.swap_remove(index)
+            }
+            _ => Self::AnyLine,
+        }
+    }
+
+    pub fn accepts(&self, corpus: &Corpus, password: &str) -> bool {
+        let lines = corpus.lines();
+        match self {
+            Self::AnyLine => lines.iter().any(|line| password.contains(line)),
+            Self::Line(line) => password.contains(lines[*line]),
+            Self::Rhyme { lines: partners, .. } => partners
+                .iter()
+                .any(|&partner| password.contains(lines[partner])),
+        }
+    }
+
+    pub fn message(&self, corpus: &Corpus, locale: Locale) -> String {
+        let name = text(locale, corpus.name, &[]);
+        match self {
+            Self::AnyLine => text(locale, "rule.corpus", &[("corpus", &name)]),
+            Self::Line(line) => text(
+                locale,
+                "rule.corpus-line",
+                &[
+                    ("corpus", &name),
+                    ("ordinal", &ordinal(*line as i128 + 1, locale)),
+                ],
+            ),
+            Self::Rhyme { word, .. } => text(
+                locale,
+                "rule.corpus-rhyme",
+                &[("corpus", &name), ("word", *word)],
+            ),
+        }
+    }
+}
+
+/// The last word of a line, without any punctuation.
+fn last_word(line: &str) -> &str {
+    line.split_whitespace()
+        .last()
+        .unwrap_or_default()
+        .trim_matches(|char: char| !char.is_alphanumeric())
+}
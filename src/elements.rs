@@ -0,0 +1,43 @@
+/// The symbol of every element, in order of atomic number.
+fn symbols() -> impl Iterator<Item = &'static str> {
+    include_str!("elements").lines()
+}
+
+/// The atomic number of the element with the given symbol.
+pub fn atomic_number(symbol: &str) -> Option<usize> {
+    symbols()
+        .position(|element| element == symbol)
+        .map(|index| index + 1)
+}
+
+/// Split some text into element symbols, with their atomic numbers. Case decides the parse: a
+/// symbol starts with a capital, so `Co` is cobalt while `CO` is carbon then oxygen. Capitals that
+/// start no symbol are kept without a number, so the parse can be explained.
+pub fn tokenise(text: &str) -> Vec<(String, Option<usize>)> {
+    let chars: Vec<_> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        if !chars[index].is_ascii_uppercase() {
+            index += 1;
+            continue;
+        }
+        // Take a following lowercase letter too, if that makes an element
+        if let Some(&next) = chars
+            .get(index + 1)
+            .filter(|next| next.is_ascii_lowercase())
+        {
+            let pair: String = [chars[index], next].iter().collect();
+            if let Some(number) = atomic_number(&pair) {
+                tokens.push((pair, Some(number)));
+                index += 2;
+                continue;
+            }
+        }
+        let single = chars[index].to_string();
+        let number = atomic_number(&single);
+        tokens.push((single, number));
+        index += 1;
+    }
+    tokens
+}
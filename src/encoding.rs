@@ -0,0 +1,104 @@
+const MORSE: [&str; 26] = [
+    ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+    "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
+];
+
+const NATO: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+/// The raised dots of each letter's Braille cell, numbered down the left column then the right.
+const BRAILLE: [&str; 26] = [
+    "1", "12", "14", "145", "15", "124", "1245", "125", "24", "245", "13", "123", "134", "1345",
+    "135", "1234", "12345", "1235", "234", "2345", "136", "1236", "2456", "1346", "13456", "1356",
+];
+
+/// The first character of the Unicode Braille Patterns block, a cell with no dots raised.
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// Look up each lowercase letter of a word in a table, skipping anything else.
+fn encode<'a>(word: &'a str, table: &'a [&'a str; 26]) -> impl Iterator<Item = &'a str> {
+    word.bytes()
+        .filter(u8::is_ascii_lowercase)
+        .map(|byte| table[usize::from(byte - b'a')])
+}
+
+/// Find the letter whose entry in a table matches.
+fn decode(table: &[&str; 26], matches: impl Fn(&str) -> bool) -> Option<char> {
+    table
+        .iter()
+        .position(|&entry| matches(entry))
+        .and_then(|index| u8::try_from(index).ok())
+        .map(|index| (b'a' + index) as char)
+}
+
+/// The offset of a Braille cell from the blank cell, given its raised dots.
+fn cell(dots: &str) -> u32 {
+    dots.bytes().fold(0, |cell, dot| cell | 1 << (dot - b'1'))
+}
+
+/// Letters in Morse code, separated by spaces, e.g. `.-- --- .-. -..`.
+pub fn to_morse(word: &str) -> String {
+    encode(word, &MORSE).collect::<Vec<_>>().join(" ")
+}
+
+/// Decode every run of Morse code in some text. Letters are separated by spaces and words by
+/// slashes, and codes that stand for no letter are dropped.
+pub fn from_morse(text: &str) -> Vec<String> {
+    text.split(|char: char| !".- /".contains(char))
+        .map(|run| {
+            run.split(['/', ' '])
+                .filter_map(|code| decode(&MORSE, |entry| entry == code))
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// A word spelled out in the NATO phonetic alphabet, e.g. `Whiskey Oscar Romeo Delta`.
+pub fn to_nato(word: &str) -> String {
+    encode(word, &NATO).collect::<Vec<_>>().join(" ")
+}
+
+/// Decode every run of NATO code words in some text, ignoring case and hyphens. The common
+/// spellings Alpha and Juliet are accepted too.
+pub fn from_nato(text: &str) -> Vec<String> {
+    let mut words = vec![String::new()];
+    for token in text.split(|char: char| !char.is_alphabetic() && char != '-') {
+        let token = token.replace('-', "").to_lowercase();
+        let letter = match token.as_str() {
+            "alpha" => Some('a'),
+            "juliet" => Some('j'),
+            _ => decode(&NATO, |entry| {
+                entry.replace('-', "").eq_ignore_ascii_case(&token)
+            }),
+        };
+        match letter {
+            Some(letter) => words.last_mut().unwrap().push(letter),
+            None if !token.is_empty() => words.push(String::new()),
+            None => {}
+        }
+    }
+    words.into_iter().filter(|word| !word.is_empty()).collect()
+}
+
+/// A word in (uncontracted) Braille, as Unicode Braille cells.
+pub fn to_braille(word: &str) -> String {
+    encode(word, &BRAILLE)
+        .filter_map(|dots| char::from_u32(BRAILLE_BLANK + cell(dots)))
+        .collect()
+}
+
+/// Decode every run of Braille letter cells in some text.
+pub fn from_braille(text: &str) -> Vec<String> {
+    let letter = |char: char| {
+        let offset = u32::from(char).checked_sub(BRAILLE_BLANK)?;
+        decode(&BRAILLE, |dots| cell(dots) == offset)
+    };
+    text.split(|char: char| letter(char).is_none())
+        .map(|run| run.chars().filter_map(letter).collect::<String>())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
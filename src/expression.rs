@@ -0,0 +1,189 @@
+use std::fmt::{self, Display, Formatter};
+
+/// An exact fraction, always kept in lowest terms with a positive denominator.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Ratio {
+    numerator: i128,
+    denominator: i128,
+}
+
+const fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Ratio {
+    /// Reduce a fraction, or give `None` if the denominator is zero (or either part is too big to
+    /// negate).
+    pub const fn new(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 || numerator == i128::MIN || denominator == i128::MIN {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Some(Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    pub const fn integer(number: i128) -> Self {
+        Self {
+            numerator: number,
+            denominator: 1,
+        }
+    }
+
+    // Every operation gives `None` rather than overflowing
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Self::new(
+            self.numerator
+                .checked_mul(other.denominator)?
+                .checked_add(other.numerator.checked_mul(self.denominator)?)?,
+            self.denominator.checked_mul(other.denominator)?,
+        )
+    }
+
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(Self {
+            numerator: other.numerator.checked_neg()?,
+            ..other
+        })
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        Self::new(
+            self.numerator.checked_mul(other.numerator)?,
+            self.denominator.checked_mul(other.denominator)?,
+        )
+    }
+
+    fn checked_div(self, other: Self) -> Option<Self> {
+        Self::new(
+            self.numerator.checked_mul(other.denominator)?,
+            self.denominator.checked_mul(other.numerator)?,
+        )
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// A recursive descent parser over `+ - * / ( )` and whole numbers.
+struct Parser<'a> {
+    chars: &'a [char],
+    position: usize,
+    /// How many binary operators have been read, since a bare number is not an expression
+    operators: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn expression(&mut self) -> Option<Ratio> {
+        let mut value = self.term()?;
+        while let Some(operator @ ('+' | '-')) = self.peek() {
+            self.position += 1;
+            self.operators += 1;
+            let term = self.term()?;
+            value = if operator == '+' {
+                value.checked_add(term)?
+            } else {
+                value.checked_sub(term)?
+            };
+        }
+        Some(value)
+    }
+
+    fn term(&mut self) -> Option<Ratio> {
+        let mut value = self.factor()?;
+        while let Some(operator @ ('*' | '/')) = self.peek() {
+            self.position += 1;
+            self.operators += 1;
+            let factor = self.factor()?;
+            value = if operator == '*' {
+                value.checked_mul(factor)?
+            } else {
+                value.checked_div(factor)?
+            };
+        }
+        Some(value)
+    }
+
+    fn factor(&mut self) -> Option<Ratio> {
+        match self.peek()? {
+            '-' => {
+                self.position += 1;
+                Ratio::integer(0).checked_sub(self.factor()?)
+            }
+            '(' => {
+                self.position += 1;
+                let value = self.expression()?;
+                (self.peek()? == ')').then_some(())?;
+                self.position += 1;
+                Some(value)
+            }
+            _ => {
+                let start = self.position;
+                while self.peek().is_some_and(|char| char.is_ascii_digit()) {
+                    self.position += 1;
+                }
+                let digits: String = self.chars[start..self.position].iter().collect();
+                digits.parse().ok().map(Ratio::integer)
+            }
+        }
+    }
+}
+
+/// Evaluate the whole of the given characters as an expression with at least one operator.
+fn evaluate(chars: &[char]) -> Option<Ratio> {
+    let mut parser = Parser {
+        chars,
+        position: 0,
+        operators: 0,
+    };
+    let value = parser.expression()?;
+    (parser.position == chars.len() && parser.operators > 0).then_some(value)
+}
+
+/// Find every maximal arithmetic expression in some text, scanning left to right, together with
+/// its exact value.
+pub fn expressions(text: &str) -> Vec<(String, Ratio)> {
+    let chars: Vec<_> = text.chars().collect();
+    let arithmetic = |char: &char| char.is_ascii_digit() || "+-*/()".contains(*char);
+    let mut found = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        if !arithmetic(&chars[start]) {
+            start += 1;
+            continue;
+        }
+        let run = chars[start..]
+            .iter()
+            .take_while(|char| arithmetic(char))
+            .count();
+        // Take the longest expression starting here, otherwise move along by one
+        match (1..=run)
+            .rev()
+            .find_map(|length| Some((length, evaluate(&chars[start..start + length])?)))
+        {
+            Some((length, value)) => {
+                found.push((chars[start..start + length].iter().collect(), value));
+                start += length;
+            }
+            None => start += 1,
+        }
+    }
+    found
+}
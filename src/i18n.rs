@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::locale::Locale;
+
+type Catalogue = HashMap<&'static str, &'static str>;
+
+/// Parse a catalogue of `key = message` lines, skipping blanks and `#` comments.
+fn parse(source: &'static str) -> Catalogue {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(" = "))
+        .collect()
+}
+
+fn catalogue(locale: Locale) -> &'static Catalogue {
+    static ENGLISH: OnceLock<Catalogue> = OnceLock::new();
+    static GERMAN: OnceLock<Catalogue> = OnceLock::new();
+    match locale {
+        Locale::English => ENGLISH.get_or_init(|| parse(include_str!("locales/en"))),
+        Locale::German => GERMAN.get_or_init(|| parse(include_str!("locales/de"))),
+    }
+}
+
+/// Look up a message and fill in its `{placeholders}`. Messages missing from a catalogue fall
+/// back to English, and then to the key itself.
+pub fn text(locale: Locale, key: &str, args: &[(&str, &str)]) -> String {
+    let template = catalogue(locale)
+        .get(key)
+        .or_else(|| catalogue(Locale::English).get(key))
+        .copied()
+        .unwrap_or(key);
+    args.iter().fold(template.to_string(), |message, (name, value)| {
+        message.replace(&format!("{{{name}}}"), value)
+    })
+}
+
+/// Like [`text`], but chooses between the `.one` and `.other` forms of a message by a count.
+pub fn plural(locale: Locale, key: &str, count: usize, args: &[(&str, &str)]) -> String {
+    let form = if count == 1 { "one" } else { "other" };
+    text(locale, &format!("{key}.{form}"), args)
+}
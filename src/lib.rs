@@ -0,0 +1,5 @@
+//! The parts of the rule engine that don't need a browser, so that other front ends, such as a
+//! command line version of the game, can share them with the web app.
+#![warn(clippy::pedantic, clippy::nursery)]
+
+pub mod encoding;
@@ -0,0 +1,107 @@
+use rand::{prelude::SliceRandom, thread_rng};
+
+use crate::settings::Difficulty;
+
+pub const PET: char = '\u{1F414}';
+pub const FOOD: char = '\u{1F41B}';
+pub const FIRE: char = '\u{1F525}';
+
+/// A pet that lives in the password, and eats some of the food there every so often.
+pub struct Pet {
+    /// Seconds between meals
+    pub interval: u32,
+    /// Seconds the pet has been in the password
+    seconds: u32,
+    /// Whether there was nothing to eat at the last meal
+    pub starved: bool,
+}
+
+impl Pet {
+    pub const fn new(difficulty: Difficulty) -> Self {
+        Self {
+            interval: match difficulty {
+                Difficulty::Easy => 30,
+                Difficulty::Normal => 20,
+                Difficulty::Hard => 10,
+            },
+            seconds: 0,
+            starved: false,
+        }
+    }
+
+    pub fn accepts(&self, password: &str) -> bool {
+        password.contains(PET) && !self.starved
+    }
+
+    /// Seconds until the next meal.
+    pub const fn remaining(&self) -> u32 {
+        self.interval - self.seconds % self.interval
+    }
+
+    /// Advance by a second. At meal times the pet eats one piece of food from the password, or
+    /// starves if there is none. A starving pet eats as soon as there is food again, and taking
+    /// the pet out and putting it back gives a fresh one.
+    pub fn tick(&mut self, password: &str) -> Option<String> {
+        if !password.contains(PET) {
+            self.seconds = 0;
+            self.starved = false;
+            return None;
+        }
+        self.seconds += 1;
+        if !self.starved && self.seconds % self.interval != 0 {
+            return None;
+        }
+        let Some(index) = password.find(FOOD) else {
+            self.starved = true;
+            return None;
+        };
+        // Count the time to the next meal from this one
+        self.seconds = 0;
+        self.starved = false;
+        let mut password = password.to_string();
+        password.remove(index);
+        Some(password)
+    }
+}
+
+/// A fire that starts somewhere in the password and burns a character a second until it is put
+/// out.
+#[derive(Default)]
+pub struct Fire {
+    started: bool,
+}
+
+impl Fire {
+    pub fn accepts(password: &str) -> bool {
+        !password.contains(FIRE)
+    }
+
+    /// Advance by a second, starting the fire the first time.
+    pub fn tick(&mut self, password: &str) -> Option<String> {
+        let mut password = password.to_string();
+        if !self.started {
+            self.started = true;
+            let positions: Vec<_> = password
+                .char_indices()
+                .map(|(index, _)| index)
+                .chain([password.len()])
+                .collect();
+            password.insert(*positions.choose(&mut thread_rng()).unwrap(), FIRE);
+            return Some(password);
+        }
+        // The first flame burns the character before it, or after it at the very start
+        let flame = password.find(FIRE)?;
+        let burnt = password[..flame]
+            .char_indices()
+            .next_back()
+            .map(|(index, _)| index)
+            .or_else(|| {
+                let after = flame + FIRE.len_utf8();
+                password[after..]
+                    .starts_with(|char: char| char != FIRE)
+                    .then_some(after)
+            })?;
+        password.remove(burnt);
+        Some(password)
+    }
+}
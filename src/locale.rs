@@ -0,0 +1,60 @@
+const STORAGE_KEY: &str = "locale";
+
+/// A language the game can be played in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Locale {
+    English,
+    German,
+}
+
+impl Locale {
+    pub const ALL: [Self; 2] = [Self::English, Self::German];
+
+    /// The BCP 47 language code.
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+        }
+    }
+
+    /// The name of the language, in that language.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+
+    /// Match a language tag such as `de-AT` by its primary subtag.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code.split(['-', '_']).next()?;
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(primary))
+    }
+
+    /// The language the player last picked, otherwise the browser's, otherwise English.
+    pub fn detect() -> Self {
+        let Some(window) = web_sys::window() else {
+            return Self::English;
+        };
+        window
+            .local_storage()
+            .ok()
+            .flatten()
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+            .or_else(|| window.navigator().language())
+            .and_then(|code| Self::from_code(&code))
+            .unwrap_or(Self::English)
+    }
+
+    /// Remember the player's choice for next time.
+    pub fn save(self) {
+        if let Some(storage) =
+            web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+        {
+            let _ = storage.set_item(STORAGE_KEY, self.code());
+        }
+    }
+}
@@ -1,13 +1,46 @@
 #![warn(clippy::pedantic, clippy::nursery)]
 
+mod audio;
+mod authenticator;
+mod autogram;
+mod checksum;
+mod chess;
+mod cipher;
+mod clock;
+mod colour;
 mod conditions;
+mod corpus;
+mod elements;
+mod expression;
+mod i18n;
+mod living;
+mod locale;
+mod maze;
+mod numerals;
+mod pattern;
+mod proof;
+mod registry;
+mod riddles;
+mod roman;
+mod settings;
+mod stages;
+mod ticker;
+mod tolerance;
+mod wordle;
 
-use crate::conditions::conditions;
+use crate::conditions::{conditions, tick_reached, username_conditions, Condition};
+use crate::i18n::text;
+use crate::locale::Locale;
+use crate::registry::Registry;
+use crate::settings::{Difficulty, Settings};
+use crate::stages::{Login, PasswordReset, Stage, TwoFactor};
+use crate::tolerance::{Timekeeper, Tolerance};
 use chrono::Local;
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::{
-    classes, function_component, html, use_effect, use_memo, use_state, virtual_dom::VNode, Html,
-    InputEvent, Properties, Renderer, TargetCast,
+    classes, context::ContextProvider, function_component, html, use_effect, use_memo,
+    use_state, virtual_dom::VNode, Callback, Event, Html, InputEvent, Properties, Renderer,
+    TargetCast,
 };
 
 #[derive(Properties, PartialEq)]
@@ -24,6 +57,37 @@ fn Error(props: &ErrorProps) -> Html {
     }
 }
 
+/// Find the index of the first condition that is not met.
+fn first_wrong(conditions: &[Condition], username: &String, password: &String) -> Option<usize> {
+    conditions
+        .iter()
+        .position(|condition| !(condition.check)(username, password))
+}
+
+/// Render every discovered condition that is not met, apart from the first one (which is shown
+/// separately).
+fn discovered_errors(
+    conditions: &[Condition],
+    discovered: &[bool],
+    wrong_index: Option<usize>,
+    username: &String,
+    password: &String,
+    locale: Locale,
+) -> Html {
+    conditions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, condition)| {
+            (discovered[index]
+                && !(condition.check)(username, password)
+                && wrong_index != Some(index))
+                .then(|| html! {
+                    <Error message={condition.render(username, password, locale)} />
+                })
+        })
+        .collect()
+}
+
 #[function_component]
 fn App() -> Html {
     // State to store the username
@@ -32,48 +96,119 @@ fn App() -> Html {
     let password = use_state(String::new);
     // State to store the confirmation password
     let confirm = use_state(String::new);
-    let won = use_state(|| false);
+    // State to store the password as it was when the account was created, which the later stages
+    // check against
+    let submitted = use_state(String::new);
+    // State to store the language the game is shown in
+    let locale = use_state(Locale::detect);
+    // State to store the player's accessibility preferences
+    let settings = use_state(Settings::load);
+    // State to store which screen the player is on
+    let stage = use_state(|| Stage::Register);
+    // Secret shared with the in-app authenticator
+    let secret = use_memo((), |()| authenticator::secret());
+    // Every account created so far, in this browser
+    let registry = use_memo((), |()| Registry::local());
+    // The clock the time-dependent rules read, which is as forgiving as the difficulty
+    let timekeeper = use_memo((), {
+        let difficulty = settings.difficulty;
+        move |()| Timekeeper::new(Tolerance::of(difficulty))
+    });
     // Generate the conditions
-    let conditions = use_memo((), |()| conditions());
+    let conditions = use_memo((), {
+        let registry = registry.clone();
+        let timekeeper = timekeeper.clone();
+        let secret = *secret;
+        // Content such as which anthem to quote is picked in the language the game starts in, and
+        // changing the difficulty only applies to the next game
+        let locale = *locale;
+        let difficulty = settings.difficulty;
+        move |()| conditions(&registry, locale, difficulty, &timekeeper, secret)
+    });
     let discovered = use_state(|| conditions.iter().map(|_| false).collect::<Vec<_>>());
+    // Generate the username conditions, which are tracked separately
+    let username_conditions = use_memo((), {
+        let registry = registry.clone();
+        move |()| username_conditions(&registry)
+    });
+    let username_discovered =
+        use_state(|| username_conditions.iter().map(|_| false).collect::<Vec<_>>());
     let time = use_state(|| Local::now().to_rfc3339());
     use_effect({
+        let timekeeper = timekeeper.clone();
         let conditions = conditions.clone();
+        let discovered = discovered.clone();
+        let username_conditions = username_conditions.clone();
         let username = username.clone();
         let password = password.clone();
         let confirm = confirm.clone();
+        let stage = stage.clone();
         move || {
             let interval = gloo_timers::callback::Interval::new(1000, move || {
                 time.set(Local::now().to_rfc3339());
-                if conditions
-                    .iter()
-                    .enumerate()
-                    .find_map(|(index, (condition, message))| {
-                        (!condition(&username, &password)).then_some((message.clone(), index))
-                    })
-                    .is_some()
-                {
+                // Once the account is created, the rules are done with the password
+                if *stage != Stage::Register {
+                    return;
+                }
+                let changed = tick_reached(
+                    &conditions,
+                    &discovered,
+                    &username,
+                    &password,
+                    &timekeeper,
+                    &username_conditions,
+                );
+                // The state handle still holds the password from before the ticks
+                let current = changed.as_ref().unwrap_or(&*password);
+                let all_met = first_wrong(&conditions, &username, current).is_none()
+                    && first_wrong(&username_conditions, &username, current).is_none();
+                if !all_met {
                     confirm.set(String::new());
                 }
+                if let Some(changed) = changed {
+                    password.set(changed);
+                }
             });
             move || drop(interval)
         }
     });
     // Find the condition that is not met and map it to the message
-    let (wrong, wrong_index) = conditions
-        .iter()
-        .enumerate()
-        .find_map(|(index, (condition, message))| {
-            (!condition(&username, &password)).then_some((message.clone(), index))
+    let wrong_index = first_wrong(&conditions, &username, &password);
+    let wrong =
+        wrong_index.map(|index| conditions[index].render(&username, &password, *locale));
+    let username_wrong_index = first_wrong(&username_conditions, &username, &password);
+    let username_wrong = username_wrong_index
+        .map(|index| username_conditions[index].render(&username, &password, *locale));
+    // Only the text of the rule the player is stuck on is read out, so that ticking widgets
+    // don't keep interrupting
+    let announcement = username_wrong_index
+        .filter(|_| !username.is_empty())
+        .map(|index| username_conditions[index].summary(*locale))
+        .or_else(|| {
+            wrong_index
+                .filter(|_| !password.is_empty())
+                .map(|index| conditions[index].summary(*locale))
         })
-        .unzip();
+        .or_else(|| {
+            (*confirm != *password && !confirm.is_empty())
+                .then(|| text(*locale, "ui.mismatch", &[]))
+        });
     let username_oninput = {
         // Clone states so we can move them into the closure
         let username = username.clone();
+        let confirm = confirm.clone();
+        let username_discovered = username_discovered.clone();
         move |event: InputEvent| {
             // Get the target of the event and dynamically cast it to an HtmlInputElement, then get
             // the value of the input and set the username state to it
             username.set(event.target_dyn_into::<HtmlInputElement>().unwrap().value());
+            confirm.set(String::new());
+            // Mark the unsatisfied username condition as discovered
+            if let Some(index) = username_wrong_index {
+                let mut cloned = username_discovered.to_vec();
+                cloned[index] = true;
+                username_discovered.set(cloned);
+            }
         }
     };
     let password_oninput = {
@@ -111,46 +246,171 @@ fn App() -> Html {
     };
 
     let submit = {
-        let won = won.clone();
+        let stage = stage.clone();
         let confirm = confirm.clone();
         let password = password.clone();
+        let username = username.clone();
+        let submitted = submitted.clone();
+        let registry = registry.clone();
+        let valid = wrong.is_none() && username_wrong.is_none();
         move |_| {
-            won.set((confirm == password) && (!password.is_empty()));
+            if valid && (confirm == password) && (!password.is_empty()) {
+                registry.insert_password(&password);
+                registry.insert_username(&username);
+                submitted.set((*password).clone());
+                stage.set(Stage::Login);
+            }
         }
     };
+    let locale_onchange = {
+        let locale = locale.clone();
+        move |event: Event| {
+            let code = event.target_dyn_into::<HtmlSelectElement>().unwrap().value();
+            if let Some(choice) = Locale::from_code(&code) {
+                choice.save();
+                locale.set(choice);
+            }
+        }
+    };
+    let colour_blind_onchange = {
+        let settings = settings.clone();
+        move |event: Event| {
+            let choice = Settings {
+                colour_blind: event.target_dyn_into::<HtmlInputElement>().unwrap().checked(),
+                ..*settings
+            };
+            choice.save();
+            settings.set(choice);
+        }
+    };
+    let difficulty_onchange = {
+        let settings = settings.clone();
+        move |event: Event| {
+            let code = event.target_dyn_into::<HtmlSelectElement>().unwrap().value();
+            if let Some(difficulty) = Difficulty::from_code(&code) {
+                let choice = Settings {
+                    difficulty,
+                    ..*settings
+                };
+                choice.save();
+                settings.set(choice);
+            }
+        }
+    };
+    // Make a callback that moves the player on to the given stage
+    let advance = |next| {
+        let stage = stage.clone();
+        Callback::from(move |()| stage.set(next))
+    };
 
     // Return some HTML
     html! {
-        <main class="flex justify-center h-full grow">
+        <ContextProvider<Locale> context={*locale}>
+        <ContextProvider<Settings> context={*settings}>
+        <main class="relative flex justify-center h-full grow">
+            <div class="absolute flex items-center gap-4 top-4 right-4">
+                <label class="flex items-center gap-2">
+                    <input
+                        type="checkbox"
+                        checked={settings.colour_blind}
+                        onchange={colour_blind_onchange}
+                    />
+                    {text(*locale, "ui.colour-blind", &[])}
+                </label>
+                <label class="flex items-center gap-2">
+                    <span class="sr-only">{text(*locale, "ui.difficulty", &[])}</span>
+                    <select
+                        onchange={difficulty_onchange}
+                        class="p-2 bg-white border border-gray-700 rounded-xl focus:outline-none"
+                    >
+                        {
+                            Difficulty::ALL.into_iter().map(|option| html! {
+                                <option value={option.code()} selected={option == settings.difficulty}>
+                                    {text(*locale, option.key(), &[])}
+                                </option>
+                            }).collect::<Html>()
+                        }
+                    </select>
+                </label>
+                <label class="flex items-center gap-2">
+                    <span class="sr-only">{text(*locale, "ui.language", &[])}</span>
+                    <select
+                        onchange={locale_onchange}
+                        class="p-2 bg-white border border-gray-700 rounded-xl focus:outline-none"
+                    >
+                        {
+                            Locale::ALL.into_iter().map(|option| html! {
+                                <option value={option.code()} selected={option == *locale}>
+                                    {option.name()}
+                                </option>
+                            }).collect::<Html>()
+                        }
+                    </select>
+                </label>
+            </div>
             <div
                 class="flex flex-col items-center justify-center w-full h-full max-w-md gap-4 px-4"
             >
                 {
-                    if *won {
-                        html! {
+                    match *stage {
+                        Stage::Login => html! {
+                            <Login password={(*submitted).clone()} onsuccess={advance(Stage::TwoFactor)} />
+                        },
+                        Stage::TwoFactor => html! {
+                            <TwoFactor secret={*secret} onsuccess={advance(Stage::Reset)} />
+                        },
+                        Stage::Reset => html! {
+                            <PasswordReset
+                                username={(*username).clone()}
+                                password={(*submitted).clone()}
+                                secret={*secret}
+                                onsuccess={advance(Stage::Taken)}
+                            />
+                        },
+                        Stage::Taken => html! {
                             <div class="relative flex flex-col w-full gap-4">
                                 <p class="p-4 text-lg text-red-500 bg-red-200 border border-red-500 rounded-xl">
-                                    {"This password is already taken. Please choose another."}
+                                    {text(*locale, "taken", &[])}
                                 </p>
                             </div>
-                        }
-                    } else {
-                        html! {
+                        },
+                        Stage::Register => html! {
                             <>
                                 <div class="relative flex flex-col w-full gap-4">
                                     <h1 class="text-2xl font-semibold">
-                                        {"Create an account."}
+                                        {text(*locale, "ui.title", &[])}
                                     </h1>
                                     <input
                                         oninput={username_oninput}
-                                        placeholder="Username"
+                                        placeholder={text(*locale, "ui.username", &[])}
                                         id="username"
                                         autocomplete="off"
                                         class="w-full p-3 text-lg transition-transform bg-white border border-gray-700 rounded-xl focus:outline-none"
                                     />
+                                    <div class="flex flex-col gap-4">
+                                        {
+                                            // Username rules are shown straight under the username
+                                            (!username.is_empty())
+                                                .then_some(())
+                                                .and(username_wrong.as_ref().map(|message| html! {
+                                                    <Error message={message} />
+                                                }))
+                                        }
+                                        {
+                                            (!username.is_empty()).then(|| discovered_errors(
+                                                &username_conditions,
+                                                &username_discovered,
+                                                username_wrong_index,
+                                                &username,
+                                                &password,
+                                                *locale,
+                                            ))
+                                        }
+                                    </div>
                                     <input
                                         oninput={password_oninput}
-                                        placeholder="Password"
+                                        value={(*password).clone()}
+                                        placeholder={text(*locale, "ui.password", &[])}
                                         type="password"
                                         id="password"
                                         autocomplete="off"
@@ -160,11 +420,12 @@ fn App() -> Html {
                                 <div class="relative flex flex-col w-full gap-4">
                                     <div class={classes!(
                                         "flex", "flex-col", "gap-4",
-                                        wrong.is_some().then_some("hidden")
+                                        (wrong.is_some() || username_wrong.is_some()).then_some("hidden")
                                     )}>
                                         <input
                                             oninput={confirm_oninput}
-                                            placeholder="Confirm password"
+                                            value={(*confirm).clone()}
+                                            placeholder={text(*locale, "ui.confirm", &[])}
                                             type="password"
                                             id="confirm"
                                             autocomplete="off"
@@ -172,11 +433,16 @@ fn App() -> Html {
                                         />
                                     </div>
                                     <button
-                                        disabled={(confirm != password) || (password.is_empty())}
+                                        disabled={
+                                            wrong.is_some()
+                                                || username_wrong.is_some()
+                                                || (confirm != password)
+                                                || (password.is_empty())
+                                        }
                                         class="p-2 transition bg-white border border-gray-700 disabled:opacity-25 disabled:pointer-events-none rounded-xl hover:bg-gray-200"
                                         onclick={submit}
                                     >
-                                        {"Submit"}
+                                        {text(*locale, "ui.submit", &[])}
                                     </button>
                                     <div class="absolute inset-x-0 flex flex-col gap-4 py-4 top-full">
                                         {
@@ -192,20 +458,14 @@ fn App() -> Html {
                                         {
                                             // Filter through the conditions and map the wrong ones to a HTML
                                             // element
-                                            conditions
-                                                .iter()
-                                                .enumerate()
-                                                .filter_map(|(index, (condition, message))|
-                                                    (
-                                                        discovered[index]
-                                                            && !condition(&username, &password)
-                                                            && wrong_index != Some(index)
-                                                            && !password.is_empty()
-                                                    )
-                                                        .then_some(html! {
-                                                            <Error message={message} />
-                                                        })
-                                                ).collect::<Vec<_>>()
+                                            (!password.is_empty()).then(|| discovered_errors(
+                                                &conditions,
+                                                &discovered,
+                                                wrong_index,
+                                                &username,
+                                                &password,
+                                                *locale,
+                                            ))
                                         }
                                     </div>
                                     <div class="absolute inset-x-0 flex flex-col gap-4 pt-4 top-full">
@@ -213,17 +473,23 @@ fn App() -> Html {
                                             (confirm != password && !confirm.is_empty())
                                                 .then_some(())
                                                 .map(|()| html! {
-                                                    <Error message="Passwords do not match." />
+                                                    <Error message={Html::from(text(*locale, "ui.mismatch", &[]))} />
                                                 })
                                         }
                                     </div>
+                                    // Announce newly failing rules to screen readers
+                                    <p aria-live="polite" class="sr-only">
+                                        {announcement.unwrap_or_default()}
+                                    </p>
                                 </div>
                             </>
-                        }
+                        },
                     }
                 }
             </div>
         </main>
+        </ContextProvider<Settings>>
+        </ContextProvider<Locale>>
     }
 }
 
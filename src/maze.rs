@@ -0,0 +1,252 @@
+use std::iter::successors;
+use std::rc::Rc;
+
+use rand::{prelude::SliceRandom, thread_rng};
+use yew::{
+    classes, function_component, html, use_context, use_state, Html, KeyboardEvent, Properties,
+};
+
+use crate::i18n::text;
+use crate::locale::Locale;
+
+const MAZE_SIZE: u32 = 20;
+const BORDER_WIDTH: u32 = 1;
+type Cell = (Position, bool);
+pub type Position = (u32, u32);
+
+fn neighbours((x, y): Position) -> Vec<Position> {
+    vec![
+        (x, y.wrapping_sub(1)),
+        (x.wrapping_add(1), y),
+        (x, y.wrapping_add(1)),
+        (x.wrapping_sub(1), y),
+    ]
+}
+
+#[repr(u8)]
+enum Direction {
+    Up = b'R',
+    Right = b'D',
+    Down = b'L',
+    Left = b'U',
+}
+
+/// A perfect maze: every pair of cells is joined by exactly one path.
+#[derive(PartialEq, Eq)]
+pub struct Maze {
+    /// Pairs of neighbouring cells with no wall between them
+    pub paths: Vec<(Position, Position)>,
+    pub start: Position,
+    pub goal: Position,
+}
+
+impl Maze {
+    pub fn generate() -> Self {
+        let mut maze: Vec<_> = (0..MAZE_SIZE)
+            .flat_map(|y| (0..MAZE_SIZE).map(move |x| ((x, y), false) as Cell))
+            .collect();
+        let mut stack = Vec::new();
+        let mut paths = Vec::new();
+        let cell = (0, 0);
+        let (_, visited) = maze
+            .iter_mut()
+            .find(|(position, _)| *position == cell)
+            .unwrap();
+        *visited = true;
+        stack.push(cell);
+        while let Some(cell) = stack.pop() {
+            let neighbours: Vec<_> = neighbours(cell)
+                .into_iter()
+                .filter_map(|neighbour| {
+                    maze.iter().find_map(|(position, visited)| {
+                        (*position == neighbour && !visited).then_some(*position)
+                    })
+                })
+                .collect();
+            if let Some(neighbour) = neighbours.choose(&mut thread_rng()) {
+                stack.push(cell);
+                paths.push((cell, *neighbour));
+                let (_, visited) = maze
+                    .iter_mut()
+                    .find(|(position, _)| *position == *neighbour)
+                    .unwrap();
+                *visited = true;
+                stack.push(*neighbour);
+            }
+        }
+        let cells: Vec<_> = maze.iter().map(|(position, _)| position).copied().collect();
+        let start = *cells.choose(&mut thread_rng()).unwrap();
+        let goal = *cells.choose(&mut thread_rng()).unwrap();
+        Self { paths, start, goal }
+    }
+
+    /// Whether there is no wall between two cells.
+    pub fn open(&self, from: Position, to: Position) -> bool {
+        self.paths
+            .iter()
+            .any(|(a, b)| *a == from && *b == to || *a == to && *b == from)
+    }
+
+    /// The moves from start to goal, spelled with the maze's (deliberately confusing) letters.
+    pub fn solution(&self) -> String {
+        let mut queue = vec![self.start];
+        let mut explored = vec![self.start];
+        let mut links = Vec::new();
+        while let Some(cell) = queue.pop() {
+            if cell == self.goal {
+                break;
+            }
+            for neighbour in neighbours(cell) {
+                if neighbour.0 < MAZE_SIZE
+                    && neighbour.1 < MAZE_SIZE
+                    && !explored.contains(&neighbour)
+                    && self.open(cell, neighbour)
+                {
+                    queue.push(neighbour);
+                    links.push((neighbour, cell));
+                    explored.push(neighbour);
+                }
+            }
+        }
+        let path: Vec<_> = successors(Some(self.goal), move |&cell| {
+            links
+                .iter()
+                .find_map(|(to, from)| (*to == cell).then_some(*from))
+        })
+        .collect();
+        let path: Vec<_> = path.iter().copied().rev().collect();
+        path.windows(2)
+            .map(|window| {
+                let [from, to] = window else { unreachable!() };
+                (if from.0 < to.0 {
+                    Direction::Right
+                } else if from.0 > to.0 {
+                    Direction::Left
+                } else if from.1 < to.1 {
+                    Direction::Down
+                } else {
+                    Direction::Up
+                }) as u8 as char
+            })
+            .collect()
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MazeGridProps {
+    pub maze: Rc<Maze>,
+}
+
+/// The maze, drawn as a grid, which can also be explored cell by cell with the arrow keys.
+#[function_component]
+pub fn MazeGrid(props: &MazeGridProps) -> Html {
+    let locale = use_context::<Locale>().unwrap_or(Locale::English);
+    let maze = &props.maze;
+    let cursor = use_state(|| maze.start);
+    let focused = use_state(|| false);
+    let onkeydown = {
+        let maze = maze.clone();
+        let cursor = cursor.clone();
+        move |event: KeyboardEvent| {
+            let (x, y) = *cursor;
+            let next = match event.key().as_str() {
+                "ArrowUp" => (x, y.wrapping_sub(1)),
+                "ArrowRight" => (x.wrapping_add(1), y),
+                "ArrowDown" => (x, y.wrapping_add(1)),
+                "ArrowLeft" => (x.wrapping_sub(1), y),
+                _ => return,
+            };
+            event.prevent_default();
+            if maze.open((x, y), next) {
+                cursor.set(next);
+            }
+        }
+    };
+    let onfocus = {
+        let focused = focused.clone();
+        move |_| focused.set(true)
+    };
+    let onblur = {
+        let focused = focused.clone();
+        move |_| focused.set(false)
+    };
+    // Describe where the cursor is and which ways it can go
+    let (x, y) = *cursor;
+    let open: Vec<_> = neighbours((x, y))
+        .into_iter()
+        .zip(["maze.up", "maze.right", "maze.down", "maze.left"])
+        .filter(|(neighbour, _)| maze.open((x, y), *neighbour))
+        .map(|(_, direction)| text(locale, direction, &[]))
+        .collect();
+    let mut announcement = vec![
+        text(
+            locale,
+            "maze.position",
+            &[
+                ("row", &(y + 1).to_string()),
+                ("column", &(x + 1).to_string()),
+            ],
+        ),
+        text(locale, "maze.open", &[("directions", &open.join(", "))]),
+    ];
+    if (x, y) == maze.start {
+        announcement.push(text(locale, "maze.start", &[]));
+    }
+    if (x, y) == maze.goal {
+        announcement.push(text(locale, "maze.goal", &[]));
+    }
+    let highlight = focused.then_some(*cursor);
+    let cells = (0..MAZE_SIZE)
+        .flat_map(|y| {
+            (0..MAZE_SIZE).map(move |x| {
+                let neighbours: Vec<_> = neighbours((x, y))
+                    .iter()
+                    .map(|neighbour| {
+                        format!(
+                            "{}px",
+                            if maze.open((x, y), *neighbour) {
+                                0
+                            } else {
+                                BORDER_WIDTH
+                            }
+                        )
+                    })
+                    .collect();
+                let borders = neighbours.join(" ");
+                html! {
+                    <div
+                        style={format!("border-width: {borders}")}
+                        class={classes!(
+                            "border-white", "size-full",
+                            ((x, y) == maze.start).then_some("bg-green-500"),
+                            ((x, y) == maze.goal).then_some("bg-red-500"),
+                            (highlight == Some((x, y))).then_some("bg-blue-400"),
+                        )}
+                    />
+                }
+            })
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+            <div class="p-4 aspect-square bg-slate-600">
+                <div
+                    role="application"
+                    tabindex="0"
+                    aria-label={text(locale, "maze.label", &[])}
+                    aria-describedby="maze-status"
+                    onkeydown={onkeydown}
+                    onfocus={onfocus}
+                    onblur={onblur}
+                    class="grid grid-cols-[repeat(20,minmax(0,1fr))] size-full focus:outline-none"
+                >
+                    {cells}
+                </div>
+            </div>
+            <p id="maze-status" class="sr-only" aria-live="polite">
+                {announcement.join(" ")}
+            </p>
+        </>
+    }
+}
@@ -0,0 +1,412 @@
+use crate::locale::Locale;
+
+const ENGLISH_UNITS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const ENGLISH_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+/// Short-scale names for successive powers of a thousand.
+const ENGLISH_SCALES: [&str; 13] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+    "septillion",
+    "octillion",
+    "nonillion",
+    "decillion",
+    "undecillion",
+];
+
+const GERMAN_UNITS: [&str; 20] = [
+    "null",
+    "eins",
+    "zwei",
+    "drei",
+    "vier",
+    "fünf",
+    "sechs",
+    "sieben",
+    "acht",
+    "neun",
+    "zehn",
+    "elf",
+    "zwölf",
+    "dreizehn",
+    "vierzehn",
+    "fünfzehn",
+    "sechzehn",
+    "siebzehn",
+    "achtzehn",
+    "neunzehn",
+];
+const GERMAN_TENS: [&str; 10] = [
+    "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig",
+];
+/// Long-scale names (singular, plural) for powers of a million and above.
+const GERMAN_SCALES: [(&str, &str); 11] = [
+    ("Million", "Millionen"),
+    ("Milliarde", "Milliarden"),
+    ("Billion", "Billionen"),
+    ("Billiarde", "Billiarden"),
+    ("Trillion", "Trillionen"),
+    ("Trilliarde", "Trilliarden"),
+    ("Quadrillion", "Quadrillionen"),
+    ("Quadrilliarde", "Quadrilliarden"),
+    ("Quintillion", "Quintillionen"),
+    ("Quintilliarde", "Quintilliarden"),
+    ("Sextillion", "Sextillionen"),
+];
+
+/// Spell out an integer in words, e.g. `42` is "forty-two" in English.
+pub fn cardinal(number: i128, locale: Locale) -> String {
+    let words = match locale {
+        Locale::English => english(number.unsigned_abs()),
+        Locale::German => german(number.unsigned_abs()),
+    };
+    // Both languages say "minus"
+    if number < 0 {
+        format!("minus {words}")
+    } else {
+        words
+    }
+}
+
+/// Spell out an ordinal in words, e.g. `42` is "forty-second" in English.
+pub fn ordinal(number: i128, locale: Locale) -> String {
+    match locale {
+        Locale::English => english_ordinal(&cardinal(number, locale)),
+        Locale::German => german_ordinal(number),
+    }
+}
+
+/// Split a number into groups of three digits, least significant first.
+fn groups(mut number: u128) -> Vec<u128> {
+    let mut groups = Vec::new();
+    while number > 0 {
+        groups.push(number % 1000);
+        number /= 1000;
+    }
+    groups
+}
+
+fn english_below_hundred(number: u128) -> String {
+    // Every index here is below 100, so the casts can't truncate
+    #[allow(clippy::cast_possible_truncation)]
+    let number = number as usize;
+    if number < 20 {
+        ENGLISH_UNITS[number].to_string()
+    } else if number % 10 == 0 {
+        ENGLISH_TENS[number / 10].to_string()
+    } else {
+        format!("{}-{}", ENGLISH_TENS[number / 10], ENGLISH_UNITS[number % 10])
+    }
+}
+
+fn english_below_thousand(number: u128) -> String {
+    match (number / 100, number % 100) {
+        (0, rest) => english_below_hundred(rest),
+        (hundreds, 0) => format!("{} hundred", english_below_hundred(hundreds)),
+        (hundreds, rest) => format!(
+            "{} hundred and {}",
+            english_below_hundred(hundreds),
+            english_below_hundred(rest)
+        ),
+    }
+}
+
+fn english(number: u128) -> String {
+    if number == 0 {
+        return ENGLISH_UNITS[0].to_string();
+    }
+    let groups = groups(number);
+    let mut words: Vec<String> = groups
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, group)| **group > 0)
+        .map(|(scale, group)| {
+            let group = english_below_thousand(*group);
+            if scale == 0 {
+                group
+            } else {
+                format!("{group} {}", ENGLISH_SCALES[scale])
+            }
+        })
+        .collect();
+    // "one thousand and five", as opposed to "one thousand five"
+    if groups.len() > 1 && (1..100).contains(&groups[0]) {
+        if let Some(last) = words.last_mut() {
+            *last = format!("and {last}");
+        }
+    }
+    words.join(" ")
+}
+
+fn english_ordinal(cardinal: &str) -> String {
+    let split = cardinal.rfind([' ', '-']).map_or(0, |index| index + 1);
+    let (head, last) = cardinal.split_at(split);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        last => last.strip_suffix('y').map_or_else(
+            || format!("{last}th"),
+            |stem| format!("{stem}ieth"),
+        ),
+    };
+    format!("{head}{last}")
+}
+
+/// Spell a number below a thousand as it appears inside a larger German compound, where a
+/// trailing one is "ein" rather than "eins".
+fn german_below_thousand(number: u128, last: bool) -> String {
+    #[allow(clippy::cast_possible_truncation)]
+    let (hundreds, rest) = ((number / 100) as usize, (number % 100) as usize);
+    let mut words = String::new();
+    if hundreds > 0 {
+        words.push_str(if hundreds == 1 {
+            "ein"
+        } else {
+            GERMAN_UNITS[hundreds]
+        });
+        words.push_str("hundert");
+    }
+    match rest {
+        0 => {}
+        1 if last => words.push_str("eins"),
+        1 => words.push_str("ein"),
+        2..=19 => words.push_str(GERMAN_UNITS[rest]),
+        _ if rest % 10 == 0 => words.push_str(GERMAN_TENS[rest / 10]),
+        _ => {
+            words.push_str(if rest % 10 == 1 {
+                "ein"
+            } else {
+                GERMAN_UNITS[rest % 10]
+            });
+            words.push_str("und");
+            words.push_str(GERMAN_TENS[rest / 10]);
+        }
+    }
+    words
+}
+
+fn german(number: u128) -> String {
+    if number == 0 {
+        return GERMAN_UNITS[0].to_string();
+    }
+    let groups = groups(number);
+    // Everything below a million is written as one word
+    let small = match (groups.first().copied(), groups.get(1).copied()) {
+        (units, Some(thousands)) if thousands > 0 => format!(
+            "{}tausend{}",
+            german_below_thousand(thousands, false),
+            german_below_thousand(units.unwrap_or(0), true)
+        ),
+        (Some(units), _) if units > 0 => german_below_thousand(units, true),
+        _ => String::new(),
+    };
+    let mut words: Vec<String> = groups
+        .iter()
+        .enumerate()
+        .skip(2)
+        .rev()
+        .filter(|(_, group)| **group > 0)
+        .map(|(scale, group)| {
+            let (singular, plural) = GERMAN_SCALES[scale - 2];
+            if *group == 1 {
+                format!("eine {singular}")
+            } else {
+                format!("{} {plural}", german_below_thousand(*group, true))
+            }
+        })
+        .collect();
+    if !small.is_empty() {
+        words.push(small);
+    }
+    words.join(" ")
+}
+
+fn german_ordinal(number: i128) -> String {
+    let words = german_ordinal_unsigned(number.unsigned_abs());
+    if number < 0 {
+        format!("minus {words}")
+    } else {
+        words
+    }
+}
+
+/// Unlike the cardinal, the ordinal is always one word, with any scale word in the singular,
+/// e.g. "zweimillionste".
+fn german_ordinal_unsigned(number: u128) -> String {
+    // Zero takes the "-te" of the small numbers, though it isn't one of them
+    if number == 0 {
+        return "nullte".to_string();
+    }
+    let groups = groups(number);
+    let big: String = groups
+        .iter()
+        .enumerate()
+        .skip(2)
+        .rev()
+        .filter(|(_, group)| **group > 0)
+        .map(|(scale, &group)| {
+            // A leading one is left out, as in "millionste"
+            let count = if group == 1 && scale + 1 == groups.len() {
+                String::new()
+            } else {
+                german_below_thousand(group, false)
+            };
+            format!("{count}{}", GERMAN_SCALES[scale - 2].0.to_lowercase())
+        })
+        .collect();
+    let small = number % 1_000_000;
+    if small == 0 {
+        // "Milliarde" loses its "e" to the ending
+        return format!("{}ste", big.strip_suffix('e').unwrap_or(&big));
+    }
+    let rest = small % 100;
+    if rest == 0 || rest >= 20 {
+        return format!("{big}{}ste", german(small));
+    }
+    // Small endings are irregular, and attach to whatever comes before them
+    let head = if small >= 100 {
+        german(small - rest)
+    } else {
+        String::new()
+    };
+    #[allow(clippy::cast_possible_truncation)]
+    let ending = match rest as usize {
+        1 => "erste".to_string(),
+        3 => "dritte".to_string(),
+        7 => "siebte".to_string(),
+        8 => "achte".to_string(),
+        rest => format!("{}te", GERMAN_UNITS[rest]),
+    };
+    format!("{big}{head}{ending}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_cardinals() {
+        for (number, words) in [
+            (0, "zero"),
+            (7, "seven"),
+            (13, "thirteen"),
+            (19, "nineteen"),
+            (40, "forty"),
+            (42, "forty-two"),
+            (100, "one hundred"),
+            (115, "one hundred and fifteen"),
+            (1000, "one thousand"),
+            (1005, "one thousand and five"),
+            (2024, "two thousand and twenty-four"),
+            (1_000_000, "one million"),
+            (3_000_200, "three million two hundred"),
+            (-5, "minus five"),
+        ] {
+            assert_eq!(cardinal(number, Locale::English), words);
+        }
+    }
+
+    #[test]
+    fn english_ordinals() {
+        for (number, words) in [
+            (0, "zeroth"),
+            (1, "first"),
+            (3, "third"),
+            (12, "twelfth"),
+            (13, "thirteenth"),
+            (20, "twentieth"),
+            (21, "twenty-first"),
+            (100, "one hundredth"),
+            (1000, "one thousandth"),
+            (1002, "one thousand and second"),
+        ] {
+            assert_eq!(ordinal(number, Locale::English), words);
+        }
+    }
+
+    #[test]
+    fn german_cardinals() {
+        for (number, words) in [
+            (0, "null"),
+            (1, "eins"),
+            (11, "elf"),
+            (17, "siebzehn"),
+            (21, "einundzwanzig"),
+            (30, "dreißig"),
+            (100, "einhundert"),
+            (101, "einhunderteins"),
+            (1000, "eintausend"),
+            (1001, "eintausendeins"),
+            (2024, "zweitausendvierundzwanzig"),
+            (1_000_000, "eine Million"),
+            (2_000_001, "zwei Millionen eins"),
+            (-5, "minus fünf"),
+        ] {
+            assert_eq!(cardinal(number, Locale::German), words);
+        }
+    }
+
+    #[test]
+    fn german_ordinals() {
+        for (number, words) in [
+            (0, "nullte"),
+            (1, "erste"),
+            (2, "zweite"),
+            (3, "dritte"),
+            (7, "siebte"),
+            (8, "achte"),
+            (16, "sechzehnte"),
+            (19, "neunzehnte"),
+            (20, "zwanzigste"),
+            (21, "einundzwanzigste"),
+            (100, "einhundertste"),
+            (101, "einhunderterste"),
+            (1000, "eintausendste"),
+            (1003, "eintausenddritte"),
+            (1_000_000, "millionste"),
+            (1_000_001, "millionerste"),
+            (2_000_000, "zweimillionste"),
+            (2_500_000, "zweimillionfünfhunderttausendste"),
+            (1_000_000_000, "milliardste"),
+            (3_000_000_005, "dreimilliardefünfte"),
+            (1_001_000_000, "milliardeeinmillionste"),
+            (-3, "minus dritte"),
+        ] {
+            assert_eq!(ordinal(number, Locale::German), words);
+        }
+    }
+}
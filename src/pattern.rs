@@ -0,0 +1,245 @@
+use std::fmt::{self, Display, Formatter};
+
+use rand::{prelude::SliceRandom, thread_rng, Rng};
+
+use crate::settings::Difficulty;
+
+/// Which characters one part of a pattern accepts.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Digit,
+    Uppercase,
+    Lowercase,
+    Vowel,
+    Literal(char),
+}
+
+impl Class {
+    fn matches(self, char: char) -> bool {
+        match self {
+            Self::Digit => char.is_ascii_digit(),
+            Self::Uppercase => char.is_ascii_uppercase(),
+            Self::Lowercase => char.is_ascii_lowercase(),
+            Self::Vowel => "aeiou".contains(char),
+            Self::Literal(literal) => char == literal,
+        }
+    }
+}
+
+/// How many times in a row one part of a pattern has to match.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Quantifier {
+    One,
+    Exactly(usize),
+    OneOrMore,
+    Optional,
+}
+
+impl Quantifier {
+    const fn bounds(self) -> (usize, usize) {
+        match self {
+            Self::One => (1, 1),
+            Self::Exactly(count) => (count, count),
+            Self::OneOrMore => (1, usize::MAX),
+            Self::Optional => (0, 1),
+        }
+    }
+}
+
+/// A small regular expression: a sequence of character classes, each with a quantifier.
+pub struct Pattern {
+    parts: Vec<(Class, Quantifier)>,
+}
+
+impl Pattern {
+    /// Easy patterns have a few fixed-length parts, normal ones may repeat, and hard ones are
+    /// longer and may have optional parts.
+    pub fn generate(difficulty: Difficulty) -> Self {
+        let mut rng = thread_rng();
+        let (length, quantifiers): (_, &[Quantifier]) = match difficulty {
+            Difficulty::Easy => (2..=3, &[Quantifier::One, Quantifier::Exactly(2)]),
+            Difficulty::Normal => (
+                3..=4,
+                &[
+                    Quantifier::One,
+                    Quantifier::Exactly(2),
+                    Quantifier::Exactly(3),
+                    Quantifier::OneOrMore,
+                ],
+            ),
+            Difficulty::Hard => (
+                4..=6,
+                &[
+                    Quantifier::One,
+                    Quantifier::Exactly(2),
+                    Quantifier::Exactly(3),
+                    Quantifier::OneOrMore,
+                    Quantifier::Optional,
+                ],
+            ),
+        };
+        let parts = (0..rng.gen_range(length))
+            .map(|index| {
+                let class = *[
+                    Class::Digit,
+                    Class::Uppercase,
+                    Class::Lowercase,
+                    Class::Vowel,
+                    Class::Literal(rng.gen_range('a'..='z')),
+                ]
+                .choose(&mut rng)
+                .unwrap();
+                let quantifier = match *quantifiers.choose(&mut rng).unwrap() {
+                    // Never let a pattern match nothing at all
+                    Quantifier::Optional if index == 0 => Quantifier::One,
+                    quantifier => quantifier,
+                };
+                (class, quantifier)
+            })
+            .collect();
+        Self { parts }
+    }
+
+    pub const fn part_count(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Try to match the parts from `part` onwards, starting at `position`, backtracking through
+    /// the quantifiers. Keeps track of the furthest it got, as (parts matched, end position).
+    fn search(&self, search: &mut Search, part: usize, position: usize) -> bool {
+        if part > search.furthest.0 || (part == search.furthest.0 && position > search.furthest.1) {
+            search.furthest = (part, position);
+        }
+        let Some(&(class, quantifier)) = self.parts.get(part) else {
+            return true;
+        };
+        // Whether the rest of the pattern can match from here doesn't depend on how we got here,
+        // so each failure only needs finding once, which keeps runs of `+` from blowing up
+        let state = part * (search.chars.len() + 1) + position;
+        if search.failed[state] {
+            return false;
+        }
+        let (min, max) = quantifier.bounds();
+        let available = search.chars[position..]
+            .iter()
+            .take(max)
+            .take_while(|&&char| class.matches(char))
+            .count();
+        // Be greedy, like most regular expression engines
+        let found = (min..=available)
+            .rev()
+            .any(|count| self.search(search, part + 1, position + count));
+        search.failed[state] = !found;
+        found
+    }
+
+    /// Search for the pattern at one starting position, returning whether the whole pattern
+    /// matched and the furthest it got.
+    fn search_from(&self, chars: &[char], start: usize) -> (bool, (usize, usize)) {
+        let mut search = Search {
+            chars,
+            failed: vec![false; self.parts.len() * (chars.len() + 1)],
+            furthest: (0, start),
+        };
+        let found = self.search(&mut search, 0, start);
+        (found, search.furthest)
+    }
+
+    /// The first substring that the whole pattern matches.
+    pub fn find(&self, text: &str) -> Option<String> {
+        let chars: Vec<_> = text.chars().collect();
+        (0..=chars.len()).find_map(|start| {
+            let (found, (_, end)) = self.search_from(&chars, start);
+            found.then(|| chars[start..end].iter().collect())
+        })
+    }
+
+    /// The substring that matches the most parts of the pattern, with how many parts it matches.
+    pub fn closest(&self, text: &str) -> Option<(String, usize)> {
+        let chars: Vec<_> = text.chars().collect();
+        (0..chars.len())
+            .map(|start| (self.search_from(&chars, start).1, start))
+            .filter(|&((parts, _), _)| parts > 0)
+            .max_by_key(|&((parts, end), start)| (parts, end - start))
+            .map(|((parts, end), start)| (chars[start..end].iter().collect(), parts))
+    }
+}
+
+/// The state of one search through some text.
+struct Search<'a> {
+    chars: &'a [char],
+    /// Which (part, position) pairs are known not to lead to a match, indexed by
+    /// `part * (chars.len() + 1) + position`
+    failed: Vec<bool>,
+    /// The furthest the search got, as (parts matched, end position)
+    furthest: (usize, usize),
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (class, quantifier) in &self.parts {
+            match class {
+                Class::Digit => write!(f, "\\d")?,
+                Class::Uppercase => write!(f, "[A-Z]")?,
+                Class::Lowercase => write!(f, "[a-z]")?,
+                Class::Vowel => write!(f, "[aeiou]")?,
+                Class::Literal(literal) => write!(f, "{literal}")?,
+            }
+            match quantifier {
+                Quantifier::One => {}
+                Quantifier::Exactly(count) => write!(f, "{{{count}}}")?,
+                Quantifier::OneOrMore => write!(f, "+")?,
+                Quantifier::Optional => write!(f, "?")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(parts: &[(Class, Quantifier)]) -> Pattern {
+        Pattern {
+            parts: parts.to_vec(),
+        }
+    }
+
+    #[test]
+    fn finds_matches() {
+        let pattern = pattern(&[
+            (Class::Uppercase, Quantifier::One),
+            (Class::Vowel, Quantifier::OneOrMore),
+            (Class::Digit, Quantifier::Exactly(2)),
+        ]);
+        assert_eq!(pattern.to_string(), "[A-Z][aeiou]+\\d{2}");
+        assert_eq!(pattern.find("xxBaa123"), Some("Baa12".to_string()));
+        assert_eq!(pattern.find("Baa1"), None);
+        assert_eq!(pattern.closest("Baa1"), Some(("Baa".to_string(), 2)));
+    }
+
+    #[test]
+    fn backtracks_through_quantifiers() {
+        let pattern = pattern(&[
+            (Class::Lowercase, Quantifier::OneOrMore),
+            (Class::Literal('a'), Quantifier::One),
+            (Class::Digit, Quantifier::Optional),
+        ]);
+        assert_eq!(pattern.find("banana"), Some("banana".to_string()));
+    }
+
+    #[test]
+    fn long_runs_fail_quickly() {
+        // Without remembering failures this takes exponential time
+        let pattern = pattern(
+            &[(Class::Lowercase, Quantifier::OneOrMore); 6]
+                .into_iter()
+                .chain([(Class::Digit, Quantifier::One)])
+                .collect::<Vec<_>>(),
+        );
+        let text = "a".repeat(200);
+        assert_eq!(pattern.find(&text), None);
+        assert_eq!(pattern.closest(&text).map(|(_, parts)| parts), Some(6));
+    }
+}
@@ -0,0 +1,48 @@
+use std::cell::RefCell;
+
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+
+use crate::registry::hex;
+use crate::settings::Difficulty;
+
+/// The SHA-256 hash of the password has to start with some hexadecimal digits.
+pub struct ProofOfWork {
+    pub prefix: String,
+    /// The last password hashed, and its hash, since every password is checked many times
+    last: RefCell<(String, String)>,
+}
+
+impl ProofOfWork {
+    /// One digit on easy, two on normal and three on hard, so a password meets the prefix about
+    /// once in 16, 256 or 4,096 tries.
+    pub fn generate(difficulty: Difficulty) -> Self {
+        let length = match difficulty {
+            Difficulty::Easy => 1,
+            Difficulty::Normal => 2,
+            Difficulty::Hard => 3,
+        };
+        let prefix = (0..length)
+            .map(|_| char::from_digit(thread_rng().gen_range(0..16), 16).unwrap())
+            .collect();
+        Self {
+            prefix,
+            last: RefCell::default(),
+        }
+    }
+
+    pub fn hash(&self, password: &str) -> String {
+        let mut last = self.last.borrow_mut();
+        if last.0 != password || last.1.is_empty() {
+            *last = (
+                password.to_string(),
+                hex(&Sha256::digest(password.as_bytes())),
+            );
+        }
+        last.1.clone()
+    }
+
+    pub fn accepts(&self, password: &str) -> bool {
+        self.hash(password).starts_with(&self.prefix)
+    }
+}
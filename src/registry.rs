@@ -0,0 +1,189 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+use web_sys::Storage;
+
+const PASSWORDS: &str = "registry.passwords";
+const USERNAMES: &str = "registry.usernames";
+
+/// Somewhere to keep lists of strings between games.
+pub trait Store {
+    fn load(&self, key: &str) -> Vec<String>;
+    fn save(&self, key: &str, values: &[String]);
+}
+
+/// A store backed by the browser's `localStorage`.
+pub struct LocalStore(Storage);
+
+impl Store for LocalStore {
+    fn load(&self, key: &str) -> Vec<String> {
+        self.0
+            .get_item(key)
+            .ok()
+            .flatten()
+            .map(|value| value.lines().map(String::from).collect())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, key: &str, values: &[String]) {
+        // The registry is best-effort, so a full or disabled storage is not an error
+        let _ = self.0.set_item(key, &values.join("\n"));
+    }
+}
+
+/// A store that forgets everything when the page is closed.
+#[derive(Default)]
+pub struct MemoryStore(RefCell<HashMap<String, Vec<String>>>);
+
+impl Store for MemoryStore {
+    fn load(&self, key: &str) -> Vec<String> {
+        self.0.borrow().get(key).cloned().unwrap_or_default()
+    }
+
+    fn save(&self, key: &str, values: &[String]) {
+        self.0.borrow_mut().insert(key.to_string(), values.to_vec());
+    }
+}
+
+/// Every account that has ever been "created": usernames, and salted hashes of passwords.
+pub struct Registry {
+    store: Box<dyn Store>,
+}
+
+impl Registry {
+    pub fn new(store: impl Store + 'static) -> Self {
+        Self {
+            store: Box::new(store),
+        }
+    }
+
+    /// Use `localStorage`, falling back to memory if the browser doesn't allow it.
+    pub fn local() -> Self {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .map_or_else(
+                || Self::new(MemoryStore::default()),
+                |storage| Self::new(LocalStore(storage)),
+            )
+    }
+
+    pub fn contains_password(&self, password: &str) -> bool {
+        self.store.load(PASSWORDS).iter().any(|entry| {
+            entry
+                .split_once(':')
+                .is_some_and(|(salt, hash)| digest(salt, password) == hash)
+        })
+    }
+
+    pub fn insert_password(&self, password: &str) {
+        if password.is_empty() || self.contains_password(password) {
+            return;
+        }
+        let salt = hex(&thread_rng().gen::<[u8; 16]>());
+        let mut entries = self.store.load(PASSWORDS);
+        entries.push(format!("{salt}:{}", digest(&salt, password)));
+        self.store.save(PASSWORDS, &entries);
+    }
+
+    pub fn contains_username(&self, username: &str) -> bool {
+        self.store
+            .load(USERNAMES)
+            .iter()
+            .any(|taken| taken.eq_ignore_ascii_case(username))
+    }
+
+    pub fn insert_username(&self, username: &str) {
+        if username.is_empty() || self.contains_username(username) {
+            return;
+        }
+        let mut usernames = self.store.load(USERNAMES);
+        usernames.push(username.to_string());
+        self.store.save(USERNAMES, &usernames);
+    }
+}
+
+fn digest(salt: &str, password: &str) -> String {
+    hex(&Sha256::new()
+        .chain_update(salt)
+        .chain_update(password)
+        .finalize())
+}
+
+/// Lowercase hexadecimal encoding of some bytes.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    /// A store that can still be looked at after a registry has taken it.
+    #[derive(Clone, Default)]
+    struct SharedStore(Rc<MemoryStore>);
+
+    impl Store for SharedStore {
+        fn load(&self, key: &str) -> Vec<String> {
+            self.0.load(key)
+        }
+
+        fn save(&self, key: &str, values: &[String]) {
+            self.0.save(key, values);
+        }
+    }
+
+    #[test]
+    fn passwords_are_salted_and_hashed() {
+        let store = SharedStore::default();
+        let registry = Registry::new(store.clone());
+        registry.insert_password("hunter2");
+        assert!(registry.contains_password("hunter2"));
+        assert!(!registry.contains_password("hunter3"));
+        let entries = store.load(PASSWORDS);
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].contains("hunter2"));
+        let (salt, hash) = entries[0].split_once(':').unwrap();
+        assert_eq!(hash, digest(salt, "hunter2"));
+    }
+
+    #[test]
+    fn the_same_password_gets_a_different_salt() {
+        let first = SharedStore::default();
+        let second = SharedStore::default();
+        Registry::new(first.clone()).insert_password("hunter2");
+        Registry::new(second.clone()).insert_password("hunter2");
+        assert_ne!(first.load(PASSWORDS), second.load(PASSWORDS));
+    }
+
+    #[test]
+    fn duplicate_passwords_are_stored_once() {
+        let store = SharedStore::default();
+        let registry = Registry::new(store.clone());
+        registry.insert_password("hunter2");
+        registry.insert_password("hunter2");
+        registry.insert_password("");
+        assert_eq!(store.load(PASSWORDS).len(), 1);
+    }
+
+    #[test]
+    fn usernames_are_unique_ignoring_case() {
+        let store = SharedStore::default();
+        let registry = Registry::new(store.clone());
+        assert!(!registry.contains_username("Alice"));
+        registry.insert_username("Alice");
+        assert!(registry.contains_username("alice"));
+        assert!(registry.contains_username("ALICE"));
+        assert!(!registry.contains_username("Bob"));
+        registry.insert_username("alice");
+        registry.insert_username("");
+        assert_eq!(store.load(USERNAMES), ["Alice"]);
+    }
+}
@@ -0,0 +1,63 @@
+use rand::{thread_rng, Rng};
+
+use crate::settings::Difficulty;
+
+/// A riddle or trivia question from the bundled content pack.
+pub struct Riddle {
+    /// Catalogue key for the question
+    pub key: String,
+    pub difficulty: Difficulty,
+    /// Every answer that counts, in any of the game's languages
+    pub answers: Vec<&'static str>,
+}
+
+/// Every riddle in the content pack.
+fn riddles() -> Vec<Riddle> {
+    include_str!("riddles")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split('|');
+            let key = format!("riddle.{}", fields.next()?);
+            let difficulty = Difficulty::from_code(fields.next()?)?;
+            Some(Riddle {
+                key,
+                difficulty,
+                answers: fields.collect(),
+            })
+        })
+        .collect()
+}
+
+/// Lowercase some text and drop the umlauts from it, so that "Münze" and "munze" match.
+fn fold(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|char| match char {
+            'ä' => 'a',
+            'ö' => 'o',
+            'ü' => 'u',
+            _ => char,
+        })
+        .collect()
+}
+
+impl Riddle {
+    /// Pick a riddle of the given difficulty, of which build.rs makes sure there is at least one.
+    pub fn choose(difficulty: Difficulty) -> Self {
+        let mut riddles = riddles();
+        riddles.retain(|riddle| riddle.difficulty == difficulty);
+        let index = thread_rng().gen_range(0..riddles.len());
+        riddles.swap_remove(index)
+    }
+
+    /// Whether some text contains an answer. Only the forms listed in the content pack count,
+    /// though any text around them is fine, so "footsteps" contains "footstep" but "still" is not
+    /// "stille".
+    pub fn accepts(&self, text: &str) -> bool {
+        let text = fold(text);
+        self.answers
+            .iter()
+            .any(|answer| text.contains(&fold(answer)))
+    }
+}
@@ -0,0 +1,94 @@
+const NUMERALS: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+const fn digit(char: char) -> Option<u32> {
+    match char {
+        'I' => Some(1),
+        'V' => Some(5),
+        'X' => Some(10),
+        'L' => Some(50),
+        'C' => Some(100),
+        'D' => Some(500),
+        'M' => Some(1000),
+        _ => None,
+    }
+}
+
+/// Write a number from 1 to 3999 in standard form.
+pub fn to_roman(mut number: u32) -> String {
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+    roman
+}
+
+/// Read a numeral, accepting only the standard form up to 3999, so `IIII`, `IC` and `MMMM` are
+/// rejected.
+pub fn from_roman(roman: &str) -> Option<u32> {
+    let digits: Vec<_> = roman.chars().map(digit).collect::<Option<_>>()?;
+    let value = digits
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| {
+            if digits.get(index + 1).is_some_and(|&next| next > value) {
+                -i64::from(value)
+            } else {
+                i64::from(value)
+            }
+        })
+        .sum::<i64>();
+    let value = u32::try_from(value)
+        .ok()
+        .filter(|value| (1..=3999).contains(value))?;
+    (to_roman(value) == roman).then_some(value)
+}
+
+/// Every maximal run of Roman numeral letters in some text, with its value if it is a valid
+/// numeral.
+pub fn numerals(text: &str) -> Vec<(String, Option<u32>)> {
+    text.split(|char| digit(char).is_none())
+        .filter(|run| !run.is_empty())
+        .map(|run| (run.to_string(), from_roman(run)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_form() {
+        assert_eq!(from_roman("XIV"), Some(14));
+        assert_eq!(from_roman("MCMXCIV"), Some(1994));
+        assert_eq!(from_roman("MMMCMXCIX"), Some(3999));
+        assert_eq!(from_roman("IIII"), None);
+        assert_eq!(from_roman("IC"), None);
+        assert_eq!(from_roman("MMMM"), None);
+        assert_eq!(from_roman(""), None);
+    }
+
+    #[test]
+    fn runs() {
+        assert_eq!(
+            numerals("aXIbLL"),
+            vec![("XI".to_string(), Some(11)), ("LL".to_string(), None)],
+        );
+    }
+}
@@ -0,0 +1,71 @@
+const COLOUR_BLIND: &str = "settings.colour-blind";
+const DIFFICULTY: &str = "settings.difficulty";
+
+/// How hard the generated rules are.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::Easy => "easy",
+            Self::Normal => "normal",
+            Self::Hard => "hard",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.code() == code)
+    }
+
+    /// Catalogue key for the name of the difficulty.
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Easy => "difficulty.easy",
+            Self::Normal => "difficulty.normal",
+            Self::Hard => "difficulty.hard",
+        }
+    }
+}
+
+/// Preferences that are remembered between games.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct Settings {
+    /// Mark colours with shapes, and offer tools instead of relying on telling colours apart
+    pub colour_blind: bool,
+    /// Applies to the rules generated for the next game
+    pub difficulty: Difficulty,
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        storage.map_or_else(Self::default, |storage| Self {
+            colour_blind: storage.get_item(COLOUR_BLIND).ok().flatten().as_deref() == Some("true"),
+            difficulty: storage
+                .get_item(DIFFICULTY)
+                .ok()
+                .flatten()
+                .and_then(|code| Difficulty::from_code(&code))
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn save(self) {
+        if let Some(storage) =
+            web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+        {
+            let _ = storage.set_item(COLOUR_BLIND, &self.colour_blind.to_string());
+            let _ = storage.set_item(DIFFICULTY, self.difficulty.code());
+        }
+    }
+}
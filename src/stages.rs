@@ -0,0 +1,299 @@
+use crate::authenticator::{self, Authenticator, Secret};
+use crate::conditions::{conditions, tick_reached, Condition};
+use crate::i18n::text;
+use crate::locale::Locale;
+use crate::registry::Registry;
+use crate::settings::Settings;
+use crate::tolerance::{Timekeeper, Tolerance};
+use crate::Error;
+use chrono::Local;
+use rand::{prelude::SliceRandom, thread_rng};
+use web_sys::HtmlInputElement;
+use yew::{
+    classes, function_component, html, use_context, use_effect, use_memo, use_state, Callback,
+    Html, InputEvent, Properties, TargetCast,
+};
+
+/// Number of freshly generated conditions the mandatory password change re-runs.
+const RESET_CONDITIONS: usize = 4;
+
+/// The screens that follow each other once the account has been "created".
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Register,
+    Login,
+    TwoFactor,
+    Reset,
+    Taken,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LoginProps {
+    pub password: String,
+    pub onsuccess: Callback<()>,
+}
+
+#[function_component]
+pub fn Login(props: &LoginProps) -> Html {
+    let locale = use_context::<Locale>().unwrap_or(Locale::English);
+    let attempt = use_state(String::new);
+    let failed = use_state(|| false);
+    let oninput = {
+        let attempt = attempt.clone();
+        let failed = failed.clone();
+        move |event: InputEvent| {
+            attempt.set(event.target_dyn_into::<HtmlInputElement>().unwrap().value());
+            failed.set(false);
+        }
+    };
+    let submit = {
+        let attempt = attempt.clone();
+        let failed = failed.clone();
+        let password = props.password.clone();
+        let onsuccess = props.onsuccess.clone();
+        move |_| {
+            if *attempt == password {
+                onsuccess.emit(());
+            } else {
+                failed.set(true);
+            }
+        }
+    };
+
+    html! {
+        <div class="relative flex flex-col w-full gap-4">
+            <p class="p-4 text-lg text-green-700 bg-green-200 border border-green-700 rounded-xl">
+                {text(locale, "login.created", &[])}
+            </p>
+            <h1 class="text-2xl font-semibold">
+                {text(locale, "login.title", &[])}
+            </h1>
+            <input
+                oninput={oninput}
+                placeholder={text(locale, "ui.password", &[])}
+                type="password"
+                id="login"
+                autocomplete="off"
+                class="w-full p-3 text-lg transition-transform bg-white border border-gray-700 rounded-xl focus:outline-none"
+            />
+            <button
+                disabled={attempt.is_empty()}
+                class="p-2 transition bg-white border border-gray-700 disabled:opacity-25 disabled:pointer-events-none rounded-xl hover:bg-gray-200"
+                onclick={submit}
+            >
+                {text(locale, "login.submit", &[])}
+            </button>
+            <div aria-live="polite">
+                {
+                    failed.then(|| html! {
+                        <Error message={Html::from(text(locale, "login.incorrect", &[]))} />
+                    })
+                }
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TwoFactorProps {
+    pub secret: Secret,
+    pub onsuccess: Callback<()>,
+}
+
+#[function_component]
+pub fn TwoFactor(props: &TwoFactorProps) -> Html {
+    let locale = use_context::<Locale>().unwrap_or(Locale::English);
+    let attempt = use_state(String::new);
+    let failed = use_state(|| false);
+    let oninput = {
+        let attempt = attempt.clone();
+        let failed = failed.clone();
+        move |event: InputEvent| {
+            attempt.set(event.target_dyn_into::<HtmlInputElement>().unwrap().value());
+            failed.set(false);
+        }
+    };
+    let submit = {
+        let attempt = attempt.clone();
+        let failed = failed.clone();
+        let secret = props.secret;
+        let onsuccess = props.onsuccess.clone();
+        move |_| {
+            if authenticator::verify(&secret, attempt.trim(), Local::now()) {
+                onsuccess.emit(());
+            } else {
+                failed.set(true);
+            }
+        }
+    };
+
+    html! {
+        <div class="relative flex flex-col w-full gap-4">
+            <h1 class="text-2xl font-semibold">
+                {text(locale, "2fa.title", &[])}
+            </h1>
+            <p>{text(locale, "2fa.prompt", &[])}</p>
+            <Authenticator secret={props.secret} />
+            <input
+                oninput={oninput}
+                placeholder={text(locale, "2fa.placeholder", &[])}
+                inputmode="numeric"
+                id="code"
+                autocomplete="off"
+                class="w-full p-3 text-lg transition-transform bg-white border border-gray-700 rounded-xl focus:outline-none"
+            />
+            <button
+                disabled={attempt.is_empty()}
+                class="p-2 transition bg-white border border-gray-700 disabled:opacity-25 disabled:pointer-events-none rounded-xl hover:bg-gray-200"
+                onclick={submit}
+            >
+                {text(locale, "2fa.submit", &[])}
+            </button>
+            <div aria-live="polite">
+                {
+                    failed.then(|| html! {
+                        <Error message={Html::from(text(locale, "2fa.invalid", &[]))} />
+                    })
+                }
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PasswordResetProps {
+    pub username: String,
+    pub password: String,
+    pub secret: Secret,
+    pub onsuccess: Callback<()>,
+}
+
+#[function_component]
+pub fn PasswordReset(props: &PasswordResetProps) -> Html {
+    let locale = use_context::<Locale>().unwrap_or(Locale::English);
+    let difficulty = use_context::<Settings>().unwrap_or_default().difficulty;
+    // Re-run a few of the rules, with freshly generated parameters, plus one that forbids reuse
+    let registry = use_memo((), |()| Registry::local());
+    let timekeeper = use_memo((), move |()| Timekeeper::new(Tolerance::of(difficulty)));
+    let conditions = use_memo(props.password.clone(), {
+        let registry = registry.clone();
+        let timekeeper = timekeeper.clone();
+        let secret = props.secret;
+        move |old: &String| {
+            let old = old.clone();
+            let mut conditions = conditions(&registry, locale, difficulty, &timekeeper, secret);
+            conditions.shuffle(&mut thread_rng());
+            conditions.truncate(RESET_CONDITIONS);
+            conditions.insert(
+                0,
+                Condition::new(
+                    move |_username, password| *password != old,
+                    |locale| text(locale, "reset.reuse", &[]).into(),
+                ),
+            );
+            conditions
+        }
+    });
+    let password = use_state(String::new);
+    let confirm = use_state(String::new);
+    let time = use_state(|| Local::now().to_rfc3339());
+    // Rules such as the fire change the password as time goes by, just as in the game itself
+    use_effect({
+        let timekeeper = timekeeper.clone();
+        let conditions = conditions.clone();
+        let username = props.username.clone();
+        let password = password.clone();
+        let confirm = confirm.clone();
+        move || {
+            let interval = gloo_timers::callback::Interval::new(1000, move || {
+                time.set(Local::now().to_rfc3339());
+                if let Some(changed) =
+                    tick_reached(&conditions, &[], &username, &password, &timekeeper, &[])
+                {
+                    password.set(changed);
+                    confirm.set(String::new());
+                }
+            });
+            move || drop(interval)
+        }
+    });
+    let wrong = conditions
+        .iter()
+        .find(|condition| !(condition.check)(&props.username, &password));
+    // Only the rule's text is read out, not any widgets that tick along with it
+    let announcement = wrong
+        .filter(|_| !password.is_empty())
+        .map(|condition| condition.summary(locale));
+    let wrong = wrong.map(|condition| condition.render(&props.username, &password, locale));
+    let password_oninput = {
+        let password = password.clone();
+        let confirm = confirm.clone();
+        move |event: InputEvent| {
+            password.set(event.target_dyn_into::<HtmlInputElement>().unwrap().value());
+            confirm.set(String::new());
+        }
+    };
+    let confirm_oninput = {
+        let confirm = confirm.clone();
+        move |event: InputEvent| {
+            confirm.set(event.target_dyn_into::<HtmlInputElement>().unwrap().value());
+        }
+    };
+    let submit = {
+        let onsuccess = props.onsuccess.clone();
+        let password = password.clone();
+        move |_| {
+            registry.insert_password(&password);
+            onsuccess.emit(());
+        }
+    };
+
+    html! {
+        <div class="relative flex flex-col w-full gap-4">
+            <h1 class="text-2xl font-semibold">
+                {text(locale, "reset.title", &[])}
+            </h1>
+            <p>{text(locale, "reset.prompt", &[])}</p>
+            <input
+                oninput={password_oninput}
+                value={(*password).clone()}
+                placeholder={text(locale, "reset.password", &[])}
+                type="password"
+                id="new-password"
+                autocomplete="off"
+                class="w-full p-3 text-lg transition-transform bg-white border border-gray-700 rounded-xl focus:outline-none"
+            />
+            <div>
+                {
+                    (!password.is_empty())
+                        .then_some(())
+                        .and(wrong.as_ref().map(|message| html! {
+                            <Error message={message.clone()} />
+                        }))
+                }
+            </div>
+            <div class={classes!(
+                "flex", "flex-col", "gap-4",
+                wrong.is_some().then_some("hidden")
+            )}>
+                <input
+                    oninput={confirm_oninput}
+                    value={(*confirm).clone()}
+                    placeholder={text(locale, "reset.confirm", &[])}
+                    type="password"
+                    id="confirm-new-password"
+                    autocomplete="off"
+                    class="w-full p-3 text-lg transition-transform bg-white border border-gray-700 rounded-xl focus:outline-none"
+                />
+            </div>
+            <button
+                disabled={wrong.is_some() || (confirm != password) || password.is_empty()}
+                class="p-2 transition bg-white border border-gray-700 disabled:opacity-25 disabled:pointer-events-none rounded-xl hover:bg-gray-200"
+                onclick={submit}
+            >
+                {text(locale, "reset.submit", &[])}
+            </button>
+            <p aria-live="polite" class="sr-only">{announcement.unwrap_or_default()}</p>
+        </div>
+    }
+}
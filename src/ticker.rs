@@ -0,0 +1,60 @@
+use rand::{prelude::SliceRandom, rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+/// The fewest of the latest prices that are accepted, so a price is always good for a few seconds
+/// after it changes.
+const GRACE: usize = 3;
+
+/// A made-up stock whose price takes a random step every second.
+pub struct Ticker {
+    pub symbol: &'static str,
+    walk: StdRng,
+    /// The latest prices in cents, newest last
+    prices: Vec<u32>,
+    /// How many of the latest prices are accepted
+    grace: usize,
+}
+
+impl Ticker {
+    pub fn generate(grace: usize) -> Self {
+        let mut rng = thread_rng();
+        Self {
+            symbol: *["PSWD", "HUNTR", "CHKN", "MOAI", "TRAM"]
+                .choose(&mut rng)
+                .unwrap(),
+            walk: StdRng::seed_from_u64(rng.gen()),
+            prices: vec![rng.gen_range(1_000..50_000)],
+            grace: grace.max(GRACE),
+        }
+    }
+
+    pub fn price(&self) -> u32 {
+        *self.prices.last().unwrap()
+    }
+
+    /// Whether the price went up on the last step.
+    pub fn rising(&self) -> bool {
+        self.prices.len() < 2 || self.prices[self.prices.len() - 2] <= self.price()
+    }
+
+    /// Take the next step of the walk.
+    pub fn tick(&mut self) {
+        let step = self.walk.gen_range(-50..=50);
+        let price = self.price().saturating_add_signed(step).max(100);
+        self.prices.push(price);
+        if self.prices.len() > self.grace {
+            self.prices.remove(0);
+        }
+    }
+
+    /// Whether the text contains the current price, or one from the last few seconds.
+    pub fn accepts(&self, text: &str) -> bool {
+        self.prices
+            .iter()
+            .any(|&price| text.contains(&dollars(price)))
+    }
+}
+
+/// A price in cents as dollars, to two decimal places, e.g. `123.45`.
+pub fn dollars(cents: u32) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
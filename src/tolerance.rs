@@ -0,0 +1,66 @@
+use std::cell::Cell;
+
+use chrono::{DateTime, Duration, Local};
+
+use crate::settings::Difficulty;
+
+/// How rules that depend on the time treat a value that has only just gone out of date.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Tolerance {
+    /// Only the current value counts
+    Strict,
+    /// Values from up to this many seconds ago count too
+    Grace(i64),
+    /// Once every rule that doesn't depend on the time is met, values from that moment count
+    /// alongside the current ones, so a finished password stays finished. The moment is
+    /// forgotten as soon as one of those rules is broken, edits included
+    Freeze,
+}
+
+impl Tolerance {
+    pub const fn of(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => Self::Freeze,
+            Difficulty::Normal => Self::Grace(30),
+            Difficulty::Hard => Self::Strict,
+        }
+    }
+}
+
+/// The clock that every time-dependent rule reads, so they all follow the same tolerance.
+pub struct Timekeeper {
+    pub tolerance: Tolerance,
+    frozen: Cell<Option<DateTime<Local>>>,
+}
+
+impl Timekeeper {
+    pub const fn new(tolerance: Tolerance) -> Self {
+        Self {
+            tolerance,
+            frozen: Cell::new(None),
+        }
+    }
+
+    pub fn frozen(&self) -> bool {
+        self.frozen.get().is_some()
+    }
+
+    /// Every moment whose value a time-dependent rule should accept right now.
+    pub fn moments(&self) -> Vec<DateTime<Local>> {
+        let now = Local::now();
+        match (self.tolerance, self.frozen.get()) {
+            (Tolerance::Grace(seconds), _) => vec![now, now - Duration::seconds(seconds)],
+            (Tolerance::Freeze, Some(frozen)) => vec![frozen, now],
+            _ => vec![now],
+        }
+    }
+
+    /// Stop the clock while every rule that doesn't depend on the time is met, and start it
+    /// again once one is not. This only does anything when the tolerance is to freeze.
+    pub fn settle(&self, others_met: bool) {
+        if self.tolerance == Tolerance::Freeze {
+            self.frozen
+                .set(others_met.then(|| self.frozen.get().unwrap_or_else(Local::now)));
+        }
+    }
+}
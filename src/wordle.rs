@@ -0,0 +1,169 @@
+use std::rc::Rc;
+
+use yew::{
+    classes, function_component, html, use_context, use_state, Html, KeyboardEvent, Properties,
+};
+
+use crate::i18n::text;
+use crate::locale::Locale;
+use crate::settings::Settings;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Grey,
+    Yellow,
+    Green,
+}
+
+/// A guessed word, with the colour each of its letters would be given.
+pub type Guess = Vec<(Colour, char)>;
+
+pub fn colour(words: &[&str], answer: &str) -> Vec<Guess> {
+    words
+        .iter()
+        .map(|word| {
+            word.chars()
+                .enumerate()
+                .map(|(index, character)| {
+                    (
+                        'colour: {
+                            if character == answer.chars().nth(index).unwrap() {
+                                break 'colour Colour::Green;
+                            }
+                            let mut wrong_word = 0;
+                            let mut wrong_guess = 0;
+                            for (answer_index, answer_character) in answer.chars().enumerate() {
+                                if answer_character == character
+                                    && word.chars().nth(answer_index).unwrap() != character
+                                {
+                                    wrong_word += 1;
+                                }
+                                if (answer_index <= index)
+                                    && (word.chars().nth(answer_index).unwrap() == character
+                                        && answer_character != character)
+                                {
+                                    wrong_guess += 1;
+                                }
+                                if answer_index >= index {
+                                    if wrong_guess == 0 {
+                                        break;
+                                    }
+                                    if wrong_guess <= wrong_word {
+                                        break 'colour Colour::Yellow;
+                                    }
+                                }
+                            }
+                            Colour::Grey
+                        },
+                        character,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct WordleGridProps {
+    pub guesses: Rc<Vec<Guess>>,
+}
+
+/// The guesses of a Wordle, which can be read tile by tile with the arrow keys.
+#[function_component]
+pub fn WordleGrid(props: &WordleGridProps) -> Html {
+    let locale = use_context::<Locale>().unwrap_or(Locale::English);
+    let settings = use_context::<Settings>().unwrap_or_default();
+    let guesses = &props.guesses;
+    let active = use_state(|| (0, 0));
+    let onkeydown = {
+        let active = active.clone();
+        let rows = guesses.len();
+        let columns = guesses.first().map_or(0, Vec::len);
+        move |event: KeyboardEvent| {
+            let (row, column): (usize, usize) = *active;
+            let next = match event.key().as_str() {
+                "ArrowUp" => (row.saturating_sub(1), column),
+                "ArrowDown" => ((row + 1).min(rows.saturating_sub(1)), column),
+                "ArrowLeft" => (row, column.saturating_sub(1)),
+                "ArrowRight" => (row, (column + 1).min(columns.saturating_sub(1))),
+                "Home" => (row, 0),
+                "End" => (row, columns.saturating_sub(1)),
+                _ => return,
+            };
+            event.prevent_default();
+            active.set(next);
+        }
+    };
+    let (active_row, active_column) = *active;
+
+    html! {
+        <div class="flex">
+            <div
+                role="grid"
+                tabindex="0"
+                aria-label={text(locale, "wordle.label", &[])}
+                aria-activedescendant={format!("wordle-{active_row}-{active_column}")}
+                onkeydown={onkeydown}
+                class="p-2 text-white group bg-slate-600 focus:outline-none"
+            >
+                {
+                    guesses.iter().enumerate().map(|(row, guess)| {
+                        html! {
+                            <div
+                                role="row"
+                                aria-label={text(locale, "wordle.guess", &[("number", &(row + 1).to_string())])}
+                                class="flex h-8"
+                            >
+                                {guess.iter().enumerate().map(|(column, (colour, character))| {
+                                    // Colour-blind mode also marks each tile with a shape
+                                    let (background, name, mark) = match colour {
+                                        Colour::Grey => ("bg-gray-500", "wordle.grey", None),
+                                        Colour::Yellow => ("bg-yellow-500", "wordle.yellow", Some('\u{25B2}')),
+                                        Colour::Green => ("bg-green-500", "wordle.green", Some('\u{25CF}')),
+                                    };
+                                    let letter = character.to_string().to_uppercase();
+                                    html! {
+                                        <div
+                                            role="gridcell"
+                                            id={format!("wordle-{row}-{column}")}
+                                            aria-label={text(
+                                                locale,
+                                                "wordle.tile",
+                                                &[("letter", &letter), ("colour", &text(locale, name, &[]))],
+                                            )}
+                                            class={classes!(
+                                                "relative", "grid", "w-8", background, "place-content-center",
+                                                ((row, column) == (active_row, active_column)).then_some(
+                                                    "group-focus:ring-2 group-focus:ring-inset group-focus:ring-white"
+                                                ),
+                                            )}
+                                        >
+                                            {letter.clone()}
+                                            {
+                                                mark.filter(|_| settings.colour_blind).map(|mark| html! {
+                                                    <span
+                                                        aria-hidden="true"
+                                                        class="absolute top-0 right-0.5 text-[0.5rem] leading-none"
+                                                    >
+                                                        {mark.to_string()}
+                                                    </span>
+                                                })
+                                            }
+                                        </div>
+                                    }
+                                }).collect::<Html>()}
+                            </div>
+                        }
+                    }).collect::<Html>()
+                }
+            </div>
+            {
+                settings.colour_blind.then(|| html! {
+                    <p aria-hidden="true" class="p-2 text-sm">
+                        {text(locale, "wordle.legend", &[])}
+                    </p>
+                })
+            }
+        </div>
+    }
+}